@@ -0,0 +1,217 @@
+use crate::{Question, TimeoutRead};
+use std::{
+    io::{self, BufRead, Write},
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    time::Duration,
+};
+
+/// What questions are displayed on and answered through, e.g. a terminal or a
+/// program driving the questions.
+///
+/// A backend is used by a [`Question`] created with [`Question::with_backend`],
+/// so that every kind of question works with every backend.
+pub trait PromptBackend {
+    /// Displays `frame`, i.e. everything a question displays at once.
+    fn render(&mut self, frame: &[u8]) -> io::Result<()>;
+
+    /// Reads the next answer and appends it, followed by a line terminator, to
+    /// `line`; returns the number of bytes appended, `0` meaning there is no
+    /// answer anymore.
+    fn read_line(&mut self, line: &mut String) -> io::Result<usize>;
+
+    /// Reads the next answer like [`PromptBackend::read_line`], giving up
+    /// after `timeout` with `None`; by default, the answer is awaited anyway,
+    /// which suits backends always having an answer at hand.
+    fn read_line_timeout(
+        &mut self,
+        line: &mut String,
+        timeout: Duration,
+    ) -> io::Result<Option<usize>> {
+        let _ = timeout;
+
+        self.read_line(line).map(Some)
+    }
+}
+
+impl<B: PromptBackend + ?Sized> PromptBackend for &mut B {
+    fn render(&mut self, frame: &[u8]) -> io::Result<()> {
+        (**self).render(frame)
+    }
+
+    fn read_line(&mut self, line: &mut String) -> io::Result<usize> {
+        (**self).read_line(line)
+    }
+
+    fn read_line_timeout(
+        &mut self,
+        line: &mut String,
+        timeout: Duration,
+    ) -> io::Result<Option<usize>> {
+        (**self).read_line_timeout(line, timeout)
+    }
+}
+
+impl<B: PromptBackend + ?Sized> PromptBackend for Box<B> {
+    fn render(&mut self, frame: &[u8]) -> io::Result<()> {
+        (**self).render(frame)
+    }
+
+    fn read_line(&mut self, line: &mut String) -> io::Result<usize> {
+        (**self).read_line(line)
+    }
+
+    fn read_line_timeout(
+        &mut self,
+        line: &mut String,
+        timeout: Duration,
+    ) -> io::Result<Option<usize>> {
+        (**self).read_line_timeout(line, timeout)
+    }
+}
+
+/// Displays the questions on a writer and reads the answers from a reader, one
+/// per line.
+pub struct Streams<R, W> {
+    reader: R,
+    writer: W,
+}
+
+impl<R: BufRead, W: Write> Streams<R, W> {
+    pub fn new(reader: R, writer: W) -> Self {
+        Self { reader, writer }
+    }
+}
+
+impl<R: BufRead, W: Write> PromptBackend for Streams<R, W> {
+    fn render(&mut self, frame: &[u8]) -> io::Result<()> {
+        self.writer.write_all(frame)?;
+        self.writer.flush()
+    }
+
+    fn read_line(&mut self, line: &mut String) -> io::Result<usize> {
+        self.reader.read_line(line)
+    }
+}
+
+/// A [`Question`] asked through a [`PromptBackend`].
+pub type BackendQuestion<B> = Question<BackendReader<B>, BackendWriter<B>>;
+
+impl<B: PromptBackend> BackendQuestion<B> {
+    pub fn with_backend(backend: B) -> Self {
+        let backend = Arc::new(Mutex::new(backend));
+        let reader = BackendReader {
+            backend: Arc::clone(&backend),
+            buffer: String::new(),
+            position: 0,
+        };
+        let writer = BackendWriter {
+            backend,
+            frame: Vec::new(),
+        };
+
+        Question::new(reader, writer)
+    }
+}
+
+fn lock<B>(backend: &Mutex<B>) -> MutexGuard<'_, B> {
+    backend.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Reads the answers of a [`BackendQuestion`] from its backend.
+pub struct BackendReader<B> {
+    backend: Arc<Mutex<B>>,
+    buffer: String,
+    position: usize,
+}
+
+impl<B: PromptBackend> io::Read for BackendReader<B> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let n = available.len().min(buf.len());
+
+        buf[..n].copy_from_slice(&available[..n]);
+        self.consume(n);
+
+        Ok(n)
+    }
+}
+
+impl<B: PromptBackend> BufRead for BackendReader<B> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.position == self.buffer.len() {
+            self.buffer.clear();
+            self.position = 0;
+            lock(&self.backend).read_line(&mut self.buffer)?;
+        }
+
+        Ok(&self.buffer.as_bytes()[self.position..])
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.position = (self.position + amount).min(self.buffer.len());
+    }
+}
+
+impl<B: PromptBackend> TimeoutRead for BackendReader<B> {
+    fn read_line_timeout(
+        &mut self,
+        buf: &mut String,
+        timeout: Duration,
+    ) -> io::Result<Option<usize>> {
+        if self.position < self.buffer.len() {
+            return self.read_line(buf).map(Some);
+        }
+
+        lock(&self.backend).read_line_timeout(buf, timeout)
+    }
+}
+
+/// Renders what a [`BackendQuestion`] displays on its backend, one frame per
+/// flush.
+pub struct BackendWriter<B> {
+    backend: Arc<Mutex<B>>,
+    frame: Vec<u8>,
+}
+
+impl<B: PromptBackend> Write for BackendWriter<B> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.frame.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if !self.frame.is_empty() {
+            lock(&self.backend).render(&self.frame)?;
+            self.frame.clear();
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn with_backend_should_ask_through_the_backend() {
+        let mut output = Vec::new();
+        let result = Question::with_backend(Streams::new("fine\n".as_bytes(), &mut output))
+            .ask("How are you?")
+            .expect("ask() should not fail");
+
+        assert_eq!(result, Some(String::from("fine")));
+        assert_eq!(output, b"How are you? ");
+    }
+
+    #[test]
+    fn with_backend_should_render_each_frame_at_once() {
+        let mut output = crate::tests::CountingWriter::default();
+
+        Question::with_backend(Streams::new("maybe\nyes\n".as_bytes(), &mut output))
+            .confirm("Sure?", true)
+            .expect("confirm() should not fail");
+
+        assert_eq!(output.writes, 2);
+    }
+}
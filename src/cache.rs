@@ -0,0 +1,57 @@
+use crate::Answers;
+use std::{
+    env, fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+/// Returns the directory where `app` caches its files, following the XDG base
+/// directory specification (or using `%LOCALAPPDATA%` on Windows).
+pub(crate) fn cache_dir(app: &str) -> Option<PathBuf> {
+    let absolute = |name| {
+        env::var_os(name)
+            .map(PathBuf::from)
+            .filter(|path| path.is_absolute())
+    };
+    let base = if cfg!(windows) {
+        absolute("LOCALAPPDATA")
+    } else {
+        absolute("XDG_CACHE_HOME").or_else(|| absolute("HOME").map(|home| home.join(".cache")))
+    };
+
+    base.map(|base| base.join(app))
+}
+
+/// Reads the answers stored in the file at `path`, one `id=answer` per line;
+/// a missing or unreadable file has no answers.
+pub(crate) fn load(path: &Path) -> Answers {
+    let mut answers = Answers::default();
+
+    if let Ok(contents) = fs::read_to_string(path) {
+        for (id, value) in contents.lines().filter_map(|line| line.split_once('=')) {
+            answers.insert(id, value.to_owned());
+        }
+    }
+
+    answers
+}
+
+/// Stores `answers` in the file at `path`, creating its directory if needed,
+/// leaving out the sensitive ones.
+pub(crate) fn save(path: &Path, answers: &Answers) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let mut contents = Vec::new();
+
+    for (id, value) in answers.iter() {
+        if answers.is_sensitive(id) {
+            continue;
+        }
+
+        writeln!(contents, "{id}={value}")?;
+    }
+
+    fs::write(path, contents)
+}
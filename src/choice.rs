@@ -0,0 +1,443 @@
+use crate::{matching::Matching, theme::Prompt, Messages, Question};
+use std::{fmt, io};
+
+/// One of the answers allowed by a choice question, displayed as `label` and
+/// resolving to `value`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Choice<T = String> {
+    pub(crate) label: String,
+    pub(crate) value: T,
+    pub(crate) aliases: Vec<String>,
+    pub(crate) group: Option<String>,
+    pub(crate) disabled: Option<String>,
+    pub(crate) description: Option<String>,
+    pub(crate) checked: bool,
+}
+
+impl Choice {
+    /// Creates a choice whose value is its label.
+    pub fn new(label: impl Into<String>) -> Self {
+        let label = label.into();
+
+        Self::with_value(label.clone(), label)
+    }
+}
+
+impl<T> Choice<T> {
+    pub fn with_value(label: impl Into<String>, value: T) -> Self {
+        Self {
+            label: label.into(),
+            value,
+            aliases: Vec::new(),
+            group: None,
+            disabled: None,
+            description: None,
+            checked: false,
+        }
+    }
+
+    /// Accepts `alias`, e.g. a single letter, as a shortcut for this choice.
+    pub fn alias(mut self, alias: impl Into<String>) -> Self {
+        self.aliases.push(alias.into());
+        self
+    }
+
+    /// Lists this choice under the `group` header, along with the adjacent
+    /// choices of the same group.
+    pub fn group(mut self, group: impl Into<String>) -> Self {
+        self.group = Some(group.into());
+        self
+    }
+
+    /// Explains this choice on a line of its own, under its label.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Keeps this choice visible but refuses to select it, explaining why.
+    pub fn disabled(mut self, reason: impl Into<String>) -> Self {
+        self.disabled = Some(reason.into());
+        self
+    }
+
+    /// Initially selects this choice in a multi-select menu.
+    pub fn checked(mut self, checked: bool) -> Self {
+        self.checked = checked;
+        self
+    }
+
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    pub fn into_value(self) -> T {
+        self.value
+    }
+
+    /// Describes why this choice can't be selected, if it is disabled.
+    pub(crate) fn unavailable(&self, messages: &Messages) -> Option<String> {
+        self.disabled.as_ref().map(|reason| {
+            messages.format(
+                "choice-unavailable",
+                &[("label", &self.label), ("reason", reason)],
+            )
+        })
+    }
+
+    pub(crate) fn matches(&self, answer: &str, matching: Matching) -> bool {
+        matching.eq(answer, &self.label)
+            || self.aliases.iter().any(|alias| matching.eq(answer, alias))
+    }
+}
+
+impl From<&str> for Choice {
+    fn from(label: &str) -> Self {
+        Self::new(label)
+    }
+}
+
+impl From<String> for Choice {
+    fn from(label: String) -> Self {
+        Self::new(label)
+    }
+}
+
+impl<T> fmt::Display for Choice<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.label)?;
+
+        if !self.aliases.is_empty() {
+            write!(f, " [{}]", self.aliases.join(", "))?;
+        }
+
+        if let Some(reason) = &self.disabled {
+            write!(f, " ({reason})")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<R: io::BufRead, W: io::Write> Question<R, W> {
+    /// Asks a question until the answer is the label of one of `choices` (or
+    /// one of its aliases), suggesting the closest choice when the answer
+    /// looks like a typo, and returns the value of the chosen one.
+    pub fn ask_choice<T>(
+        &mut self,
+        question: impl Into<String>,
+        choices: impl IntoIterator<Item = impl Into<Choice<T>>>,
+    ) -> io::Result<Option<T>> {
+        let mut choices: Vec<Choice<T>> = choices.into_iter().map(Into::into).collect();
+        let listed: Vec<String> = choices.iter().map(Choice::to_string).collect();
+        let question = format!("{} ({})", question.into(), listed.join("/"));
+
+        self.validating(|this| loop {
+            let Some(answer) = this.ask_as(Prompt::Select, question.as_str())? else {
+                return Ok(None);
+            };
+
+            if let Some(index) = choices
+                .iter()
+                .position(|choice| choice.matches(&answer, this.matching))
+            {
+                match choices[index].unavailable(&this.messages) {
+                    Some(message) => this.write_error(&message)?,
+                    None => return Ok(Some(choices.swap_remove(index).value)),
+                }
+
+                continue;
+            }
+
+            let typo = mistyped(&answer, &choices, this.matching);
+
+            if let (Some(index), false) = (typo, this.matching.confirm_typos) {
+                return Ok(Some(choices.swap_remove(index).value));
+            }
+
+            match typo.or_else(|| closest_match(&answer, &choices)) {
+                Some(index) => {
+                    let suggestion = this
+                        .messages
+                        .format("choice-suggestion", &[("label", &choices[index].label)]);
+
+                    match this.confirm(suggestion, true)? {
+                        Some(true) => return Ok(Some(choices.swap_remove(index).value)),
+                        Some(false) => {}
+                        None => return Ok(None),
+                    }
+                }
+                None => {
+                    let labels: Vec<&str> = available(&choices)
+                        .map(|(_, choice)| choice.label())
+                        .collect();
+
+                    let message = this
+                        .messages
+                        .format("choice-invalid", &[("labels", &labels.join(", "))]);
+
+                    this.write_error(&message)?;
+                }
+            }
+        })
+    }
+}
+
+fn available<T>(choices: &[Choice<T>]) -> impl Iterator<Item = (usize, &Choice<T>)> {
+    choices
+        .iter()
+        .enumerate()
+        .filter(|(_, choice)| choice.disabled.is_none())
+}
+
+/// Returns the index of the available choice closest to `answer`, provided it
+/// is close enough for `answer` to be a typo of its label.
+fn closest_match<T>(answer: &str, choices: &[Choice<T>]) -> Option<usize> {
+    available(choices)
+        .map(|(index, choice)| (edit_distance(answer, &choice.label), index, choice))
+        .filter(|(distance, _, choice)| *distance <= (choice.label.chars().count() / 2).max(1))
+        .min_by_key(|(distance, _, _)| *distance)
+        .map(|(_, index, _)| index)
+}
+
+/// Returns the index of the single available choice closest to `answer`
+/// within the typo tolerance of `matching`.
+fn mistyped<T>(answer: &str, choices: &[Choice<T>], matching: Matching) -> Option<usize> {
+    let answer = matching.comparable(answer);
+    let distances: Vec<(usize, usize)> = available(choices)
+        .map(|(index, choice)| {
+            let distance = edit_distance(&answer, &matching.comparable(&choice.label));
+
+            (distance, index)
+        })
+        .filter(|(distance, _)| *distance <= matching.typo_tolerance)
+        .collect();
+    let closest = distances.iter().map(|(distance, _)| *distance).min()?;
+
+    match distances
+        .iter()
+        .filter(|(distance, _)| *distance == closest)
+        .collect::<Vec<_>>()[..]
+    {
+        [(_, index)] => Some(*index),
+        _ => None,
+    }
+}
+
+/// Computes the Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, a) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+
+        for (j, b) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a != *b);
+
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ENVIRONMENT: &str = "Environment?";
+    const ENVIRONMENTS: [&str; 2] = ["production", "staging"];
+
+    fn ask_choice(input: &str) -> (Option<String>, String) {
+        ask_choice_among(input, ENVIRONMENTS.map(Choice::from))
+    }
+
+    fn ask_choice_among(
+        input: &str,
+        choices: impl IntoIterator<Item = Choice>,
+    ) -> (Option<String>, String) {
+        let mut output = Vec::new();
+        let result = Question::new(input.as_bytes(), &mut output)
+            .ask_choice(ENVIRONMENT, choices)
+            .expect("ask_choice() should not fail");
+
+        (
+            result,
+            String::from_utf8(output).expect("from_utf8() should not fail"),
+        )
+    }
+
+    #[test]
+    fn ask_choice_should_display_the_choices() {
+        let (_, output) = ask_choice("staging\n");
+
+        assert_eq!(output, "Environment? (production/staging) ");
+    }
+
+    #[test]
+    fn ask_choice_should_return_a_valid_choice() {
+        let (result, _) = ask_choice("staging\n");
+
+        assert_eq!(result, Some(String::from("staging")));
+    }
+
+    #[test]
+    fn ask_choice_should_suggest_the_closest_choice() {
+        let (result, output) = ask_choice("stagin\n\n");
+
+        assert_eq!(result, Some(String::from("staging")));
+        assert!(output.ends_with("Did you mean 'staging'? [Y/n] "));
+    }
+
+    #[test]
+    fn ask_choice_should_ask_again_when_the_suggestion_is_declined() {
+        let (result, _) = ask_choice("stagin\nn\nproduction\n");
+
+        assert_eq!(result, Some(String::from("production")));
+    }
+
+    #[test]
+    fn ask_choice_should_list_the_choices_when_nothing_is_close() {
+        let (result, output) = ask_choice("qa\nstaging\n");
+
+        assert_eq!(result, Some(String::from("staging")));
+        assert!(output.contains("Please answer one of production, staging.\n"));
+    }
+
+    #[test]
+    fn ask_choice_should_replace_the_previous_attempt_in_ansi_mode() {
+        let mut output = Vec::new();
+
+        Question::new("qa\nqa\nstaging\n".as_bytes(), &mut output)
+            .ansi(true)
+            .echo_answers(false)
+            .ask_choice(ENVIRONMENT, ENVIRONMENTS.map(Choice::from))
+            .expect("ask_choice() should not fail");
+        let output = String::from_utf8(output).expect("from_utf8() should not fail");
+
+        assert!(output.ends_with(
+            "\x1b[2A\r\x1b[JPlease answer one of production, staging.\n\
+             Environment? (production/staging) "
+        ));
+        assert_eq!(output.matches("\x1b[2A").count(), 1);
+    }
+
+    #[test]
+    fn ask_choice_should_display_the_aliases() {
+        let (_, output) = ask_choice_among(
+            "p\n",
+            [Choice::new("production").alias("p"), Choice::new("staging")],
+        );
+
+        assert_eq!(output, "Environment? (production [p]/staging) ");
+    }
+
+    #[test]
+    fn ask_choice_should_accept_an_alias() {
+        let (result, _) = ask_choice_among(
+            "s\n",
+            [
+                Choice::new("production").alias("p"),
+                Choice::new("staging").alias("s"),
+            ],
+        );
+
+        assert_eq!(result, Some(String::from("staging")));
+    }
+
+    #[test]
+    fn ask_choice_should_refuse_a_disabled_choice() {
+        let (result, output) = ask_choice_among(
+            "staging\nproduction\n",
+            [
+                Choice::new("production"),
+                Choice::new("staging").disabled("requires --experimental"),
+            ],
+        );
+
+        assert_eq!(result, Some(String::from("production")));
+        assert!(output.contains("'staging' is unavailable: requires --experimental.\n"));
+    }
+
+    #[test]
+    fn ask_choice_should_return_the_value_of_the_chosen_choice() {
+        let result = Question::new("Staging (eu-west-1)\n".as_bytes(), io::sink())
+            .ask_choice(
+                ENVIRONMENT,
+                [
+                    Choice::with_value("Production (us-east-1)", 1),
+                    Choice::with_value("Staging (eu-west-1)", 2),
+                ],
+            )
+            .expect("ask_choice() should not fail");
+
+        assert_eq!(result, Some(2));
+    }
+
+    #[test]
+    fn ask_choice_should_ignore_the_case_and_accents_when_asked_to() {
+        let result = Question::new("RECETTE\n".as_bytes(), io::sink())
+            .ignore_case(true)
+            .fold_accents(true)
+            .ask_choice(ENVIRONMENT, ["production", "récette"])
+            .expect("ask_choice() should not fail");
+
+        assert_eq!(result, Some(String::from("récette")));
+    }
+
+    #[test]
+    fn ask_choice_should_resolve_a_typo_within_the_tolerance() {
+        let mut output = Vec::new();
+        let result = Question::new("stagin\n".as_bytes(), &mut output)
+            .typo_tolerance(1)
+            .ask_choice(ENVIRONMENT, ENVIRONMENTS)
+            .expect("ask_choice() should not fail");
+
+        assert_eq!(result, Some(String::from("staging")));
+        assert_eq!(output, b"Environment? (production/staging) ");
+    }
+
+    #[test]
+    fn ask_choice_should_not_resolve_a_typo_close_to_several_choices() {
+        let result = Question::new("tast\n".as_bytes(), io::sink())
+            .typo_tolerance(1)
+            .ask_choice(ENVIRONMENT, ["test", "tart"])
+            .expect("ask_choice() should not fail");
+
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn ask_choice_should_confirm_a_typo_when_asked_to() {
+        let (result, output) = {
+            let mut output = Vec::new();
+            let result = Question::new("stagin\ny\n".as_bytes(), &mut output)
+                .typo_tolerance(1)
+                .confirm_typos(true)
+                .ask_choice(ENVIRONMENT, ENVIRONMENTS)
+                .expect("ask_choice() should not fail");
+
+            (
+                result,
+                String::from_utf8(output).expect("from_utf8() should not fail"),
+            )
+        };
+
+        assert_eq!(result, Some(String::from("staging")));
+        assert!(output.ends_with("Did you mean 'staging'? [Y/n] "));
+    }
+
+    #[test]
+    fn edit_distance_should_count_the_edits_between_two_strings() {
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("same", "same"), 0);
+    }
+}
@@ -0,0 +1,134 @@
+use crate::{Color, ColorDepth, Question};
+use std::{error, fmt, io, io::Write, str::FromStr};
+
+/// The error of a color which isn't written `#rrggbb`, `#rgb` or `rgb(r, g, b)`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseColorError;
+
+impl fmt::Display for ParseColorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid color")
+    }
+}
+
+impl error::Error for ParseColorError {}
+
+impl FromStr for Color {
+    type Err = ParseColorError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        let source = source.trim();
+
+        if let Some(hex) = source.strip_prefix('#') {
+            let digits: Vec<u8> = hex
+                .chars()
+                .map(|c| c.to_digit(16).map(|digit| digit as u8))
+                .collect::<Option<_>>()
+                .ok_or(ParseColorError)?;
+
+            return match digits[..] {
+                [r, g, b] => Ok(Color::rgb(r * 17, g * 17, b * 17)),
+                [r1, r2, g1, g2, b1, b2] => {
+                    Ok(Color::rgb(r1 * 16 + r2, g1 * 16 + g2, b1 * 16 + b2))
+                }
+                _ => Err(ParseColorError),
+            };
+        }
+
+        let components = source
+            .strip_prefix("rgb(")
+            .and_then(|source| source.strip_suffix(')'))
+            .ok_or(ParseColorError)?
+            .split(',')
+            .map(|component| component.trim().parse::<u8>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| ParseColorError)?;
+
+        match components[..] {
+            [r, g, b] => Ok(Color::rgb(r, g, b)),
+            _ => Err(ParseColorError),
+        }
+    }
+}
+
+impl<R: io::BufRead, W: io::Write> Question<R, W> {
+    /// Asks for a color, written `#rrggbb`, `#rgb` or `rgb(r, g, b)`; on true
+    /// color terminals, a swatch of the color is displayed for confirmation.
+    pub fn ask_color(&mut self, question: impl Into<String>) -> io::Result<Option<Color>> {
+        let question = question.into();
+
+        self.validating(|this| loop {
+            let Some(answer) = this.ask(question.as_str())? else {
+                return Ok(None);
+            };
+
+            let Ok(color) = answer.parse::<Color>() else {
+                let message = this.messages.format("color-invalid", &[]);
+
+                this.write_error(&message)?;
+                continue;
+            };
+
+            if !this.decorated() || this.color_depth != ColorDepth::TrueColor {
+                return Ok(Some(color));
+            }
+
+            let Color { r, g, b } = color;
+
+            write!(this.frame, "\x1b[48;2;{r};{g};{b}m    \x1b[0m ")?;
+
+            let confirmation = this.messages.format("color-confirm", &[]);
+
+            match this.confirm(confirmation, true)? {
+                Some(true) => return Ok(Some(color)),
+                Some(false) => {}
+                None => return Ok(None),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_str_should_parse_the_color_notations() {
+        assert_eq!("#ff8700".parse(), Ok(Color::rgb(255, 135, 0)));
+        assert_eq!("#f80".parse(), Ok(Color::rgb(255, 136, 0)));
+        assert_eq!("rgb(255, 135, 0)".parse(), Ok(Color::rgb(255, 135, 0)));
+        assert_eq!("orange".parse::<Color>(), Err(ParseColorError));
+        assert_eq!("rgb(256, 0, 0)".parse::<Color>(), Err(ParseColorError));
+    }
+
+    #[test]
+    fn ask_color_should_ask_again_for_an_invalid_color() {
+        let mut output = Vec::new();
+        let result = Question::new("orange\n#ff8700\n".as_bytes(), &mut output)
+            .ask_color("Accent?")
+            .expect("ask_color() should not fail");
+
+        assert_eq!(result, Some(Color::rgb(255, 135, 0)));
+        assert_eq!(
+            output,
+            b"Accent? Please answer a color such as #ff8700 or rgb(255, 135, 0).\nAccent? "
+        );
+    }
+
+    #[test]
+    fn ask_color_should_confirm_the_swatch_on_true_color_terminals() {
+        let mut output = Vec::new();
+        let result = Question::new("#ff8700\n\n".as_bytes(), &mut output)
+            .ansi(true)
+            .echo_answers(false)
+            .color_depth(ColorDepth::TrueColor)
+            .ask_color("Accent?")
+            .expect("ask_color() should not fail");
+
+        assert_eq!(result, Some(Color::rgb(255, 135, 0)));
+        assert_eq!(
+            String::from_utf8(output).expect("from_utf8() should not fail"),
+            "Accent? \x1b[48;2;255;135;0m    \x1b[0m Use this color? [Y/n] "
+        );
+    }
+}
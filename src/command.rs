@@ -0,0 +1,173 @@
+use crate::{matching::Matching, theme::Prompt, Question};
+use std::io::{self, Write};
+
+impl<R: io::BufRead, W: io::Write> Question<R, W> {
+    /// Displays `command` quoted for a shell, in bold on terminals accepting
+    /// ANSI escape sequences, and asks to confirm running it, which defaults
+    /// to no; when `editable`, answering `e` asks for the command line to run
+    /// instead, which replaces `command` before asking again.
+    pub fn confirm_command(
+        &mut self,
+        question: impl Into<String>,
+        command: &mut Vec<String>,
+        editable: bool,
+    ) -> io::Result<Option<bool>> {
+        let hint = match editable {
+            true => "command-hint-editable",
+            false => "confirm-hint-no",
+        };
+        let question = format!("{} {}", question.into(), self.messages.format(hint, &[]));
+        let yes = self.messages.list("confirm-yes");
+        let no = self.messages.list("confirm-no");
+        let edit = self.messages.list("command-edit-keywords");
+        let matching = Matching {
+            ignore_case: true,
+            ..self.matching
+        };
+        let is = |list: &[String], answer: &str| list.iter().any(|item| matching.eq(answer, item));
+
+        self.validating(|this| loop {
+            let line = quote(command);
+
+            match this.decorated() {
+                true => writeln!(this.frame, "\x1b[1m$ {line}\x1b[22m")?,
+                false => writeln!(this.frame, "$ {line}")?,
+            }
+
+            let Some(answer) = this.ask_as(Prompt::Confirm, question.as_str())? else {
+                return Ok(None);
+            };
+
+            if is(&yes, &answer) {
+                return Ok(Some(true));
+            } else if answer.is_empty() || is(&no, &answer) {
+                return Ok(Some(false));
+            } else if editable && is(&edit, &answer) {
+                let prompt = this.messages.format("command-edit", &[("command", &line)]);
+
+                match this.ask(prompt)? {
+                    Some(edited) if !edited.is_empty() => *command = split(&edited),
+                    Some(_) => {}
+                    None => return Ok(None),
+                }
+
+                continue;
+            }
+
+            let id = match editable {
+                true => "command-invalid",
+                false => "confirm-invalid",
+            };
+            let message = this.messages.format(id, &[]);
+
+            this.write_error(&message)?;
+        })
+    }
+}
+
+/// Quotes the arguments of `command` for a POSIX shell, leaving those made of
+/// safe characters only as is.
+fn quote(command: &[String]) -> String {
+    let safe = |c: char| c.is_ascii_alphanumeric() || "_@%+=:,./-".contains(c);
+
+    command
+        .iter()
+        .map(|argument| {
+            if !argument.is_empty() && argument.chars().all(safe) {
+                argument.clone()
+            } else {
+                format!("'{}'", argument.replace('\'', r"'\''"))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Splits a command line into its arguments, honoring single quotes, double
+/// quotes and backslashes the way a POSIX shell does.
+fn split(line: &str) -> Vec<String> {
+    let mut arguments = Vec::new();
+    let mut argument: Option<String> = None;
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => arguments.extend(argument.take()),
+            '\'' => {
+                let argument = argument.get_or_insert_with(String::new);
+
+                argument.extend(chars.by_ref().take_while(|c| *c != '\''));
+            }
+            '"' => {
+                let argument = argument.get_or_insert_with(String::new);
+
+                while let Some(c) = chars.next() {
+                    match c {
+                        '"' => break,
+                        '\\' => argument.extend(chars.next()),
+                        c => argument.push(c),
+                    }
+                }
+            }
+            '\\' => argument
+                .get_or_insert_with(String::new)
+                .extend(chars.next()),
+            c => argument.get_or_insert_with(String::new).push(c),
+        }
+    }
+
+    arguments.extend(argument);
+    arguments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command() -> Vec<String> {
+        ["git", "commit", "-m", "Don't panic"]
+            .map(String::from)
+            .to_vec()
+    }
+
+    #[test]
+    fn quote_and_split_should_round_trip_the_arguments() {
+        let line = quote(&command());
+
+        assert_eq!(line, r"git commit -m 'Don'\''t panic'");
+        assert_eq!(split(&line), command());
+        assert_eq!(
+            split(r#"echo "a \"b\"" c\ d ''"#),
+            ["echo", r#"a "b""#, "c d", ""]
+        );
+    }
+
+    #[test]
+    fn confirm_command_should_display_the_quoted_command() {
+        let mut output = Vec::new();
+        let result = Question::new("y\n".as_bytes(), &mut output)
+            .confirm_command("Run it?", &mut command(), false)
+            .expect("confirm_command() should not fail");
+
+        assert_eq!(result, Some(true));
+        assert_eq!(
+            String::from_utf8(output).expect("from_utf8() should not fail"),
+            "$ git commit -m 'Don'\\''t panic'\nRun it? [y/N] "
+        );
+    }
+
+    #[test]
+    fn confirm_command_should_let_the_command_be_edited() {
+        let mut command = command();
+        let mut output = Vec::new();
+        let result = Question::new("e\ngit commit --amend\ny\n".as_bytes(), &mut output)
+            .confirm_command("Run it?", &mut command, true)
+            .expect("confirm_command() should not fail");
+
+        assert_eq!(result, Some(true));
+        assert_eq!(command, ["git", "commit", "--amend"]);
+        assert!(String::from_utf8(output)
+            .expect("from_utf8() should not fail")
+            .ends_with("$ git commit --amend\nRun it? [y/N/e] "));
+    }
+}
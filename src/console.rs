@@ -0,0 +1,173 @@
+use crate::PromptBackend;
+use std::{collections::VecDeque, io, thread};
+
+/// A [`PromptBackend`] for tests, checking that the questions are asked in the
+/// expected order and answering each of them.
+///
+/// The test fails with a diff as soon as a question doesn't contain the text
+/// it is expected to, is asked while none is expected, or when the console is
+/// dropped with expected questions left unasked.
+#[derive(Debug, Default)]
+pub struct TestConsole {
+    expectations: VecDeque<(String, Option<String>)>,
+    asked: usize,
+    /// What was rendered since the last answer.
+    rendered: String,
+}
+
+/// A question expected by a [`TestConsole`], waiting for its response.
+#[must_use = "an expected question needs a response"]
+pub struct Expectation {
+    console: TestConsole,
+    question: String,
+}
+
+impl TestConsole {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Expects the next question to contain `question`.
+    pub fn expect(self, question: impl Into<String>) -> Expectation {
+        Expectation {
+            console: self,
+            question: question.into(),
+        }
+    }
+}
+
+impl Expectation {
+    pub fn respond(self, answer: impl Into<String>) -> TestConsole {
+        self.push(Some(answer.into()))
+    }
+
+    /// Responds with the end of input, as when the user presses Ctrl+D.
+    pub fn respond_eof(self) -> TestConsole {
+        self.push(None)
+    }
+
+    fn push(mut self, answer: Option<String>) -> TestConsole {
+        self.console.expectations.push_back((self.question, answer));
+        self.console
+    }
+}
+
+impl PromptBackend for TestConsole {
+    fn render(&mut self, frame: &[u8]) -> io::Result<()> {
+        self.rendered.push_str(&String::from_utf8_lossy(frame));
+        Ok(())
+    }
+
+    fn read_line(&mut self, line: &mut String) -> io::Result<usize> {
+        let rendered = std::mem::take(&mut self.rendered);
+
+        self.asked += 1;
+
+        let Some((question, answer)) = self.expectations.pop_front() else {
+            panic!(
+                "question #{} was not expected:\n{}",
+                self.asked,
+                diff("", &rendered)
+            );
+        };
+
+        if !rendered.contains(&question) {
+            panic!(
+                "question #{} diverged from the expected one:\n{}",
+                self.asked,
+                diff(&question, &rendered)
+            );
+        }
+
+        Ok(match answer {
+            Some(answer) => {
+                line.push_str(&answer);
+                line.push('\n');
+                answer.len() + 1
+            }
+            None => 0,
+        })
+    }
+}
+
+impl Drop for TestConsole {
+    fn drop(&mut self) {
+        if thread::panicking() || self.expectations.is_empty() {
+            return;
+        }
+
+        let unasked: Vec<String> = self
+            .expectations
+            .iter()
+            .map(|(question, _)| format!("- {question}"))
+            .collect();
+
+        panic!(
+            "{} expected question(s) were not asked:\n{}",
+            unasked.len(),
+            unasked.join("\n")
+        );
+    }
+}
+
+/// Lays out the `expected` and `rendered` lines one above the other.
+fn diff(expected: &str, rendered: &str) -> String {
+    let mut diff = String::new();
+
+    for line in expected.lines() {
+        diff.push_str(&format!("- {line}\n"));
+    }
+
+    for line in rendered.lines() {
+        diff.push_str(&format!("+ {line}\n"));
+    }
+
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Form, Question};
+
+    #[test]
+    fn test_console_should_answer_the_expected_questions() {
+        let mut console = TestConsole::new()
+            .expect("name?")
+            .respond("Alice")
+            .expect("Port?")
+            .respond("");
+        let answers = Form::new()
+            .field("name", "Your name?")
+            .field_with_default("port", "Port?", "8080")
+            .ask(&mut Question::with_backend(&mut console));
+        let answers = answers.expect("ask() should not fail");
+
+        assert_eq!(answers.get("name"), Some("Alice"));
+        assert_eq!(answers.get("port"), Some("8080"));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "question #1 diverged from the expected one:\n- Port?\n+ Your name? "
+    )]
+    fn test_console_should_fail_when_a_question_diverges() {
+        let mut console = TestConsole::new().expect("Port?").respond("4242");
+
+        let _ = Question::with_backend(&mut console).ask("Your name?");
+    }
+
+    #[test]
+    #[should_panic(expected = "question #1 was not expected")]
+    fn test_console_should_fail_when_a_question_is_not_expected() {
+        let mut console = TestConsole::new();
+
+        let _ = Question::with_backend(&mut console).ask("Your name?");
+    }
+
+    #[test]
+    #[should_panic(expected = "1 expected question(s) were not asked:\n- Port?")]
+    fn test_console_should_fail_when_an_expected_question_is_not_asked() {
+        TestConsole::new().expect("Port?").respond_eof();
+    }
+}
@@ -0,0 +1,142 @@
+use crate::Question;
+use std::{fmt, io, str::FromStr};
+
+/// A conversion of the answer, failing with the message to display.
+type Convert<'q, T> = Box<dyn Fn(&str) -> Result<T, String> + 'q>;
+
+/// A question whose answer goes through a chain of fallible conversions,
+/// built with [`Question::parse`]; the error of the failing step is displayed
+/// before asking again.
+pub struct Conversion<'q, R, W, T> {
+    question: &'q mut Question<R, W>,
+    text: String,
+    convert: Convert<'q, T>,
+}
+
+impl<R: io::BufRead, W: io::Write> Question<R, W> {
+    /// Starts a chain of conversions of the answer to `question`, parsed as
+    /// a `T` first.
+    pub fn parse<'q, T: FromStr + 'q>(
+        &'q mut self,
+        question: impl Into<String>,
+    ) -> Conversion<'q, R, W, T>
+    where
+        T::Err: fmt::Display,
+    {
+        Conversion {
+            question: self,
+            text: question.into(),
+            convert: Box::new(|answer| answer.parse().map_err(|error: T::Err| error.to_string())),
+        }
+    }
+}
+
+impl<'q, R: io::BufRead, W: io::Write, T: 'q> Conversion<'q, R, W, T> {
+    /// Converts the value of the previous step with `convert`, e.g.
+    /// `Port::try_from`.
+    pub fn try_map<U, E: fmt::Display>(
+        self,
+        convert: impl Fn(T) -> Result<U, E> + 'q,
+    ) -> Conversion<'q, R, W, U> {
+        let previous = self.convert;
+
+        Conversion {
+            question: self.question,
+            text: self.text,
+            convert: Box::new(move |answer| {
+                previous(answer).and_then(|value| convert(value).map_err(|error| error.to_string()))
+            }),
+        }
+    }
+
+    /// Asks the question until its answer goes through every conversion.
+    pub fn ask(self) -> io::Result<Option<T>> {
+        let Self {
+            question,
+            text,
+            convert,
+        } = self;
+
+        question.validating(|question| loop {
+            let Some(answer) = question.ask(text.as_str())? else {
+                return Ok(None);
+            };
+
+            match convert(&answer) {
+                Ok(value) => return Ok(Some(value)),
+                Err(message) => question.write_error(&message)?,
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Lockout, Question, QuestionError};
+
+    #[derive(Debug, PartialEq)]
+    struct Port(u16);
+
+    impl TryFrom<u64> for Port {
+        type Error = &'static str;
+
+        fn try_from(port: u64) -> Result<Self, Self::Error> {
+            match u16::try_from(port) {
+                Ok(0) | Err(_) => Err("ports range from 1 to 65535"),
+                Ok(port) => Ok(Self(port)),
+            }
+        }
+    }
+
+    #[test]
+    fn ask_should_replace_the_previous_attempt_in_ansi_mode() {
+        let mut output = Vec::new();
+
+        Question::new("http\n70000\n8080\n".as_bytes(), &mut output)
+            .ansi(true)
+            .echo_answers(false)
+            .parse::<u64>("Port?")
+            .try_map(Port::try_from)
+            .ask()
+            .expect("ask() should not fail");
+        let output = String::from_utf8(output).expect("from_utf8() should not fail");
+
+        assert_eq!(
+            output,
+            "Port? \x1b[1A\r\x1b[Jinvalid digit found in string\n\
+             Port? \x1b[2A\r\x1b[Jports range from 1 to 65535\nPort? "
+        );
+    }
+
+    #[test]
+    fn ask_should_abort_after_too_many_refused_answers() {
+        let error = Question::new("http\n70000\n8080\n".as_bytes(), std::io::sink())
+            .lockout(Lockout::Abort { after: 2 })
+            .parse::<u64>("Port?")
+            .try_map(Port::try_from)
+            .ask()
+            .expect_err("ask() should fail");
+
+        assert!(matches!(
+            QuestionError::from(error),
+            QuestionError::LockedOut
+        ));
+    }
+
+    #[test]
+    fn try_map_should_display_the_error_of_the_failing_step() {
+        let mut output = Vec::new();
+        let result = Question::new("http\n70000\n8080\n".as_bytes(), &mut output)
+            .parse::<u64>("Port?")
+            .try_map(Port::try_from)
+            .ask()
+            .expect("ask() should not fail");
+
+        assert_eq!(result, Some(Port(8080)));
+        assert_eq!(
+            output,
+            b"Port? invalid digit found in string\nPort? ports range from 1 to 65535\nPort? "
+                .as_slice()
+        );
+    }
+}
@@ -0,0 +1,476 @@
+use crate::{json, Answers, Form};
+use std::io;
+
+/// The value of a key, either a basic string or an array of them.
+enum Value {
+    String(String),
+    Array(Vec<String>),
+}
+
+/// The keys of the `[[field]]` and `[[section.field]]` tables.
+const FIELD_KEYS: [&str; 5] = ["id", "question", "default", "type", "choices"];
+/// The keys of the `[[section]]` tables.
+const SECTION_KEYS: [&str; 3] = ["id", "title", "when"];
+
+/// A table of a definition, along with what it is called in errors, e.g.
+/// `field 2`.
+struct Table {
+    name: String,
+    entries: Vec<(String, Value)>,
+}
+
+impl Table {
+    fn new(kind: &str, number: usize) -> Self {
+        Self {
+            name: format!("{kind} {number}"),
+            entries: Vec::new(),
+        }
+    }
+
+    fn take(&mut self, key: &str) -> Option<Value> {
+        let index = self.entries.iter().position(|(name, _)| name == key)?;
+
+        Some(self.entries.remove(index).1)
+    }
+
+    /// Returns the string `key`, failing if it is an array.
+    fn string(&mut self, key: &str) -> io::Result<Option<String>> {
+        match self.take(key) {
+            Some(Value::String(value)) => Ok(Some(value)),
+            Some(Value::Array(_)) => {
+                Err(self.invalid(&format!("expected `{key}` to be a basic string")))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn invalid(&self, message: &str) -> io::Error {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{}: {message}", self.name),
+        )
+    }
+}
+
+/// The table the following keys belong to.
+#[derive(Clone, Copy)]
+enum Current {
+    Field,
+    Section,
+    SectionField,
+}
+
+/// The tables of a definition: the top-level fields, then the sections along
+/// with their own fields.
+#[derive(Default)]
+struct Definition {
+    fields: Vec<Table>,
+    sections: Vec<(Table, Vec<Table>)>,
+    count: usize,
+}
+
+impl Form {
+    /// Builds a form from a definition written in a subset of TOML, so that
+    /// its questions can be edited without recompiling.
+    ///
+    /// Each question is a `[[field]]` table with an `id`, a `question` and an
+    /// optional `default`, all of them basic strings, along with an optional
+    /// `type`, one of `text`, `integer`, `natural`, `number` and `bool` (see
+    /// [`Form::parsed`]), and optional `choices`, an array of basic strings
+    /// (see [`Form::choices`]). Each key is set once; a `#` outside a string
+    /// starts a comment.
+    ///
+    /// A `[[section]]` table, with an `id`, a `title` and an optional `when`
+    /// condition, either `id == value` or `id != value`, starts a section
+    /// (see [`Form::section_if`]) whose questions are the `[[section.field]]`
+    /// tables following it:
+    ///
+    /// ```toml
+    /// [[field]]
+    /// id = "port"
+    /// question = "Port?"
+    /// type = "natural"
+    /// default = "8080" # asked when left blank
+    ///
+    /// [[field]]
+    /// id = "tls"
+    /// question = "TLS?"
+    /// choices = ["yes", "no"]
+    ///
+    /// [[section]]
+    /// id = "tls"
+    /// title = "TLS"
+    /// when = "tls == yes"
+    ///
+    /// [[section.field]]
+    /// id = "cert"
+    /// question = "Certificate?"
+    /// ```
+    pub fn from_toml(source: &str) -> io::Result<Self> {
+        let mut definition = Definition::default();
+        let mut current = None;
+
+        for (number, line) in source.lines().enumerate() {
+            let line = uncommented(line).trim();
+            let invalid = |message: &str| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("line {}: {message}", number + 1),
+                )
+            };
+
+            if line.is_empty() {
+                continue;
+            }
+
+            match line {
+                "[[field]]" => {
+                    definition.count += 1;
+                    definition
+                        .fields
+                        .push(Table::new("field", definition.count));
+                    current = Some(Current::Field);
+                    continue;
+                }
+                "[[section]]" => {
+                    let table = Table::new("section", definition.sections.len() + 1);
+
+                    definition.sections.push((table, Vec::new()));
+                    current = Some(Current::Section);
+                    continue;
+                }
+                "[[section.field]]" => {
+                    let (_, fields) = definition
+                        .sections
+                        .last_mut()
+                        .ok_or_else(|| invalid("expected `[[section]]` first"))?;
+
+                    definition.count += 1;
+                    fields.push(Table::new("field", definition.count));
+                    current = Some(Current::SectionField);
+                    continue;
+                }
+                _ => {}
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| invalid("expected a table or `key = value`"))?;
+            let key = key.trim();
+            let value = parse_value(value.trim())
+                .ok_or_else(|| invalid("expected a basic string or an array of them"))?;
+            let (table, keys) = current
+                .and_then(|current| definition.table(current))
+                .ok_or_else(|| invalid("expected `[[field]]` first"))?;
+
+            if !keys.contains(&key) {
+                return Err(invalid(&format!("unknown key `{key}`")));
+            }
+
+            if table.entries.iter().any(|(name, _)| name == key) {
+                return Err(invalid(&format!("duplicate key `{key}`")));
+            }
+
+            table.entries.push((key.to_owned(), value));
+        }
+
+        definition.build()
+    }
+}
+
+impl Definition {
+    /// Returns the `current` table, along with its keys.
+    fn table(&mut self, current: Current) -> Option<(&mut Table, &'static [&'static str])> {
+        match current {
+            Current::Field => self.fields.last_mut().map(|table| (table, &FIELD_KEYS[..])),
+            Current::Section => self
+                .sections
+                .last_mut()
+                .map(|(table, _)| (table, &SECTION_KEYS[..])),
+            Current::SectionField => self
+                .sections
+                .last_mut()
+                .and_then(|(_, fields)| fields.last_mut())
+                .map(|table| (table, &FIELD_KEYS[..])),
+        }
+    }
+
+    fn build(self) -> io::Result<Form> {
+        let mut form = build_fields(self.fields)?;
+
+        for (mut section, fields) in self.sections {
+            let id = section.string("id")?;
+            let title = section.string("title")?;
+            let when = section.string("when")?;
+            let (Some(id), Some(title)) = (id, title) else {
+                return Err(section.invalid("expected an `id` and a `title`"));
+            };
+            let fields = build_fields(fields)?;
+
+            form = match when {
+                Some(when) => {
+                    let condition = parse_condition(&when).ok_or_else(|| {
+                        section.invalid("expected `when` to be `id == value` or `id != value`")
+                    })?;
+
+                    form.section_if(id, title, fields, condition)
+                }
+                None => form.section(id, title, fields),
+            };
+        }
+
+        Ok(form)
+    }
+}
+
+fn build_fields(tables: Vec<Table>) -> io::Result<Form> {
+    tables.into_iter().try_fold(Form::new(), |form, mut table| {
+        let id = table.string("id")?;
+        let question = table.string("question")?;
+        let default = table.string("default")?;
+        let kind = table.string("type")?;
+        let choices = match table.take("choices") {
+            Some(Value::Array(choices)) => Some(choices),
+            Some(Value::String(_)) => {
+                return Err(table.invalid("expected `choices` to be an array"));
+            }
+            None => None,
+        };
+        let (Some(id), Some(question)) = (id, question) else {
+            return Err(table.invalid("expected an `id` and a `question`"));
+        };
+
+        let form = match default {
+            Some(default) => form.field_with_default(id, question, default),
+            None => form.field(id, question),
+        };
+        let form = match kind.as_deref() {
+            None | Some("text") => form,
+            Some("integer") => form.parsed::<i64>(),
+            Some("natural") => form.parsed::<u64>(),
+            Some("number") => form.parsed::<f64>(),
+            Some("bool") => form.parsed::<bool>(),
+            Some(kind) => return Err(table.invalid(&format!("unknown type `{kind}`"))),
+        };
+
+        Ok(match choices {
+            Some(choices) => form.choices(choices),
+            None => form,
+        })
+    })
+}
+
+/// Parses a basic string or an array of them.
+fn parse_value(source: &str) -> Option<Value> {
+    let Some(items) = source.strip_prefix('[') else {
+        return json::parse_string(source).map(Value::String);
+    };
+    let mut items = items.strip_suffix(']')?.trim();
+    let mut values = Vec::new();
+
+    while !items.is_empty() {
+        let end = string_end(items)?;
+
+        values.push(json::parse_string(&items[..end])?);
+        items = items[end..].trim_start();
+        items = match items.strip_prefix(',') {
+            Some(rest) => rest.trim_start(),
+            None if items.is_empty() => items,
+            None => return None,
+        };
+    }
+
+    Some(Value::Array(values))
+}
+
+/// Returns the length of the basic string `source` starts with.
+fn string_end(source: &str) -> Option<usize> {
+    let mut escaped = false;
+
+    for (index, char) in source.char_indices().skip(1) {
+        match char {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '"' => return Some(index + 1),
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// Parses `id == value` or `id != value` into a condition on the answers.
+fn parse_condition(source: &str) -> Option<impl Fn(&Answers) -> bool + Send + Sync + 'static> {
+    let (equal, (id, value)) = match source.split_once("!=") {
+        Some(parts) => (false, parts),
+        None => (true, source.split_once("==")?),
+    };
+    let (id, value) = (id.trim().to_owned(), value.trim().to_owned());
+
+    (!id.is_empty())
+        .then_some(move |answers: &Answers| (answers.get(&id) == Some(value.as_str())) == equal)
+}
+
+/// Returns `line` up to its comment, if any.
+fn uncommented(line: &str) -> &str {
+    let (mut quoted, mut escaped) = (false, false);
+
+    for (index, char) in line.char_indices() {
+        match char {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            '#' if !quoted => return &line[..index],
+            _ => {}
+        }
+    }
+
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Question;
+
+    #[test]
+    fn from_toml_should_build_the_fields() {
+        let form = Form::from_toml(
+            r#"
+            # Deployment
+            [[field]] # who
+            id = "name"
+            question = "Your \"name\"? #1"
+
+            [[field]]
+            id = "port"
+            question = "Port?"
+            default = "8080" # the usual one
+            "#,
+        )
+        .expect("from_toml() should not fail");
+        let mut output = Vec::new();
+        let answers = form
+            .ask(&mut Question::new("Alice\n\n".as_bytes(), &mut output))
+            .expect("ask() should not fail");
+
+        assert_eq!(answers.get("name"), Some("Alice"));
+        assert_eq!(answers.get("port"), Some("8080"));
+        assert_eq!(output, b"Your \"name\"? #1 Port? (default: 8080) ");
+    }
+
+    #[test]
+    fn from_toml_should_refuse_the_answers_not_of_the_type() {
+        let form =
+            Form::from_toml("[[field]]\nid = \"port\"\nquestion = \"Port?\"\ntype = \"natural\"\n")
+                .expect("from_toml() should not fail");
+        let mut output = Vec::new();
+        let answers = form
+            .ask(&mut Question::new("http\n80\n".as_bytes(), &mut output))
+            .expect("ask() should not fail");
+
+        assert_eq!(answers.get("port"), Some("80"));
+        assert_eq!(output, b"Port? 'http' is not a valid answer.\nPort? ");
+    }
+
+    #[test]
+    fn from_toml_should_refuse_the_answers_not_among_the_choices() {
+        let form = Form::from_toml(
+            "[[field]]\nid = \"tls\"\nquestion = \"TLS?\"\nchoices = [\"yes\", \"no\"]\n",
+        )
+        .expect("from_toml() should not fail");
+        let mut output = Vec::new();
+        let answers = form
+            .ask(&mut Question::new("maybe\nno\n".as_bytes(), &mut output))
+            .expect("ask() should not fail");
+
+        assert_eq!(answers.get("tls"), Some("no"));
+        assert_eq!(
+            output,
+            b"TLS? (yes/no) Please answer one of yes, no.\nTLS? (yes/no) "
+        );
+    }
+
+    #[test]
+    fn from_toml_should_ask_the_sections_whose_condition_holds() {
+        let form = Form::from_toml(
+            r#"
+            [[field]]
+            id = "tls"
+            question = "TLS?"
+
+            [[section]]
+            id = "tls"
+            title = "TLS"
+            when = "tls == yes"
+
+            [[section.field]]
+            id = "cert"
+            question = "Certificate?"
+
+            [[section]]
+            id = "plain"
+            title = "Plain"
+            when = "tls != yes"
+
+            [[section.field]]
+            id = "port"
+            question = "Port?"
+            "#,
+        )
+        .expect("from_toml() should not fail");
+        let answers = form
+            .ask(&mut Question::new("yes\ncert.pem\n".as_bytes(), io::sink()))
+            .expect("ask() should not fail");
+
+        assert_eq!(
+            answers.section("tls").and_then(|tls| tls.get("cert")),
+            Some("cert.pem")
+        );
+        assert!(answers.section("plain").is_none());
+    }
+
+    #[test]
+    fn from_toml_should_reject_an_invalid_definition() {
+        let error = Form::from_toml("[[field]]\nid = \"name\"\nkind = \"text\"\n")
+            .expect_err("from_toml() should fail");
+
+        assert_eq!(error.to_string(), "line 3: unknown key `kind`");
+    }
+
+    #[test]
+    fn from_toml_should_reject_a_duplicate_key() {
+        let error = Form::from_toml("[[field]]\nid = \"name\"\nid = \"port\"\n")
+            .expect_err("from_toml() should fail");
+
+        assert_eq!(error.to_string(), "line 3: duplicate key `id`");
+    }
+
+    #[test]
+    fn from_toml_should_reject_an_unknown_type() {
+        let error =
+            Form::from_toml("[[field]]\nid = \"port\"\nquestion = \"Port?\"\ntype = \"port\"\n")
+                .expect_err("from_toml() should fail");
+
+        assert_eq!(error.to_string(), "field 1: unknown type `port`");
+    }
+
+    #[test]
+    fn from_toml_should_reject_a_field_without_a_question() {
+        let error =
+            Form::from_toml("[[field]]\nid = \"name\"\n").expect_err("from_toml() should fail");
+
+        assert_eq!(
+            error.to_string(),
+            "field 1: expected an `id` and a `question`"
+        );
+    }
+
+    #[test]
+    fn from_toml_should_reject_a_section_field_without_a_section() {
+        let error = Form::from_toml("[[section.field]]\nid = \"name\"\n")
+            .expect_err("from_toml() should fail");
+
+        assert_eq!(error.to_string(), "line 1: expected `[[section]]` first");
+    }
+}
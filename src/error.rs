@@ -0,0 +1,50 @@
+use crate::Answers;
+use std::{error, fmt, io};
+
+#[derive(Debug)]
+pub enum QuestionError {
+    Io(io::Error),
+    /// The deadline elapsed before every question was answered; holds the
+    /// answers given so far.
+    DeadlineExceeded(Answers),
+    /// The question was cancelled while waiting for an answer.
+    Cancelled,
+    /// The answer was refused too many times, as set by
+    /// [`Lockout::Abort`](crate::Lockout::Abort); the questions returning an
+    /// [`io::Error`] wrap it in one of kind [`io::ErrorKind::PermissionDenied`].
+    LockedOut,
+}
+
+impl fmt::Display for QuestionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => error.fmt(f),
+            Self::DeadlineExceeded(_) => f.write_str("the deadline elapsed before the end"),
+            Self::Cancelled => f.write_str("the question was cancelled"),
+            Self::LockedOut => f.write_str("too many refused answers"),
+        }
+    }
+}
+
+impl error::Error for QuestionError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for QuestionError {
+    fn from(error: io::Error) -> Self {
+        let locked_out = error
+            .get_ref()
+            .and_then(|error| error.downcast_ref::<Self>())
+            .is_some_and(|error| matches!(error, Self::LockedOut));
+
+        match locked_out {
+            true => Self::LockedOut,
+            false => Self::Io(error),
+        }
+    }
+}
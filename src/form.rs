@@ -0,0 +1,1731 @@
+use crate::{cache, secret::Wiped, theme::Prompt, Messages, Question, QuestionError, TimeoutRead};
+use std::{
+    env, fmt, fs,
+    io::{self, Write},
+    path::PathBuf,
+    str::FromStr,
+    time::{Duration, Instant, SystemTime},
+};
+
+/// What a [`Form`] does with its remaining questions once its deadline elapsed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Expiry {
+    /// Resolve the remaining questions to their defaults, failing if one of
+    /// them has none.
+    UseDefaults,
+    /// Fail with the answers given so far.
+    Abort,
+}
+
+/// A sequence of identified questions asked one after the other.
+#[derive(Debug, Default)]
+pub struct Form {
+    fields: Vec<Field>,
+    clear_screen: bool,
+    header: Option<String>,
+    cache: Option<PathBuf>,
+    state: Option<PathBuf>,
+    undo: Option<String>,
+    review: bool,
+    env_prefix: Option<String>,
+    sections: Vec<Section>,
+}
+
+/// Tells from the answers given so far whether to ask a section.
+type Condition = Box<dyn Fn(&Answers) -> bool + Send + Sync>;
+
+/// A form asked after the questions of its parent, under its title.
+struct Section {
+    id: String,
+    title: String,
+    form: Form,
+    condition: Condition,
+    /// Whether the section was added by [`Form::section_if`].
+    conditional: bool,
+}
+
+impl fmt::Debug for Section {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Section")
+            .field("id", &self.id)
+            .field("title", &self.title)
+            .field("form", &self.form)
+            .field("conditional", &self.conditional)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug)]
+struct Field {
+    id: String,
+    question: String,
+    default: Option<String>,
+    tags: Vec<String>,
+    sensitive: bool,
+    metadata: Vec<(String, String)>,
+    parse: Option<Parse>,
+    choices: Vec<String>,
+}
+
+/// The type the answers to a question must parse as, set by [`Form::parsed`].
+struct Parse {
+    parses: Box<dyn Fn(&str) -> bool + Send + Sync>,
+    /// The id of the message describing what the type expects.
+    constraint: &'static str,
+    message: Option<String>,
+}
+
+impl fmt::Debug for Parse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Parse")
+            .field("constraint", &self.constraint)
+            .field("message", &self.message)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Field {
+    fn new(id: String, question: String, default: Option<String>) -> Self {
+        Self {
+            id,
+            question,
+            default,
+            tags: Vec::new(),
+            sensitive: false,
+            metadata: Vec::new(),
+            parse: None,
+            choices: Vec::new(),
+        }
+    }
+
+    /// Tells whether `answer` is one of the choices of the question, if any.
+    fn is_choice(&self, answer: &str) -> bool {
+        self.choices.is_empty() || self.choices.iter().any(|choice| choice == answer)
+    }
+
+    /// Tells whether `answer` is a choice of the question and parses as its
+    /// type.
+    fn accepts(&self, answer: &str) -> bool {
+        self.is_choice(answer)
+            && self
+                .parse
+                .as_ref()
+                .is_none_or(|parse| (parse.parses)(answer))
+    }
+
+    /// Returns the message refusing `answer`, unless the question accepts it.
+    fn refusal(&self, answer: &str, messages: &Messages) -> Option<String> {
+        if !self.is_choice(answer) {
+            return Some(
+                messages.format("choice-invalid", &[("labels", &self.choices.join(", "))]),
+            );
+        }
+
+        let parse = self
+            .parse
+            .as_ref()
+            .filter(|parse| !(parse.parses)(answer))?;
+
+        Some(match &parse.message {
+            Some(message) => {
+                crate::fill_message(message, answer, &messages.format(parse.constraint, &[]))
+            }
+            None => messages.format("invalid-answer", &[("value", &answer)]),
+        })
+    }
+
+    /// Returns the default answer, the remembered one taking precedence.
+    fn default<'a>(&'a self, remembered: &'a Answers) -> Option<&'a str> {
+        remembered.get(&self.id).or(self.default.as_deref())
+    }
+
+    /// Returns the question along with its choices and the hint of its
+    /// default, masked when sensitive, e.g. once reviewed.
+    fn question(&self, default: Option<&str>, messages: &Messages) -> String {
+        let question = match self.choices.is_empty() {
+            true => self.question.clone(),
+            false => format!("{} ({})", self.question, self.choices.join("/")),
+        };
+
+        match default {
+            Some(_) if self.sensitive => {
+                let redacted = messages.format("redacted", &[]);
+                let hint = messages.format("default-hint", &[("default", &redacted)]);
+
+                format!("{question} {hint}")
+            }
+            Some(default) => {
+                let hint = messages.format("default-hint", &[("default", &default)]);
+
+                format!("{question} {hint}")
+            }
+            None => question,
+        }
+    }
+}
+
+impl Form {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn field(mut self, id: impl Into<String>, question: impl Into<String>) -> Self {
+        self.fields
+            .push(Field::new(id.into(), question.into(), None));
+        self
+    }
+
+    /// Adds a question whose empty answer resolves to `default`.
+    pub fn field_with_default(
+        mut self,
+        id: impl Into<String>,
+        question: impl Into<String>,
+        default: impl Into<String>,
+    ) -> Self {
+        self.fields
+            .push(Field::new(id.into(), question.into(), Some(default.into())));
+        self
+    }
+
+    /// Tags the last added question, e.g. `advanced`, to find it with
+    /// [`Form::tagged`] or filter the [`AnsweredQuestion`] records.
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        if let Some(field) = self.fields.last_mut() {
+            field.tags.push(tag.into());
+        }
+        self
+    }
+
+    /// Attaches the `key` metadata to the last added question, e.g. the
+    /// section of a wizard it belongs to, to read it with [`Form::metadata`]
+    /// or from the [`AnsweredQuestion`] records.
+    pub fn meta(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        if let Some(field) = self.fields.last_mut() {
+            field.metadata.push((key.into(), value.into()));
+        }
+        self
+    }
+
+    /// Marks the last added question as sensitive, e.g. asking for a token:
+    /// its answer is replaced with the `redacted` message, i.e. `«redacted»`,
+    /// in the review screen, the hint of its default and the records, is left
+    /// out of the exports, and is neither remembered nor resumed.
+    pub fn sensitive(mut self) -> Self {
+        if let Some(field) = self.fields.last_mut() {
+            field.sensitive = true;
+        }
+        self
+    }
+
+    /// Refuses the answers to the last added question which don't parse as a
+    /// `T`, e.g. `u16`, displaying the `invalid-answer` message and asking it
+    /// again; the preset answers which don't parse are asked again too.
+    pub fn parsed<T: FromStr>(self) -> Self {
+        self.parse::<T>(None)
+    }
+
+    /// Refuses the answers like [`Form::parsed`] with `message`, where
+    /// `{value}` stands for the answer and `{constraint}` for what a `T`
+    /// is, e.g. "a whole number".
+    pub fn parsed_with_message<T: FromStr>(self, message: impl Into<String>) -> Self {
+        self.parse::<T>(Some(message.into()))
+    }
+
+    fn parse<T: FromStr>(mut self, message: Option<String>) -> Self {
+        if let Some(field) = self.fields.last_mut() {
+            field.parse = Some(Parse {
+                parses: Box::new(|answer| answer.parse::<T>().is_ok()),
+                constraint: constraint::<T>(),
+                message,
+            });
+        }
+        self
+    }
+
+    /// Refuses the answers to the last added question which aren't one of
+    /// `choices`, listed after the question, displaying the `choice-invalid`
+    /// message and asking it again.
+    pub fn choices(mut self, choices: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        if let Some(field) = self.fields.last_mut() {
+            field.choices = choices.into_iter().map(Into::into).collect();
+        }
+        self
+    }
+
+    /// Returns the ids of the questions, in order.
+    pub fn ids(&self) -> impl Iterator<Item = &str> {
+        self.fields.iter().map(|field| field.id.as_str())
+    }
+
+    /// Returns the ids of the questions tagged with `tag`, in order.
+    pub fn tagged<'a>(&'a self, tag: &'a str) -> impl Iterator<Item = &'a str> {
+        self.fields
+            .iter()
+            .filter(move |field| field.tags.iter().any(|t| t == tag))
+            .map(|field| field.id.as_str())
+    }
+
+    /// Returns the tags of the question `id`.
+    pub fn tags(&self, id: &str) -> &[String] {
+        self.find(id).map_or(&[], |field| &field.tags)
+    }
+
+    /// Returns the `key` metadata of the question `id`.
+    pub fn metadata(&self, id: &str, key: &str) -> Option<&str> {
+        self.find(id)?
+            .metadata
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Sums up how the questions were answered, e.g. to print how long the
+    /// setup took or to log where users struggle.
+    pub fn stats(&self, answers: &Answers) -> Stats {
+        let records = answers.records();
+
+        Stats {
+            prompts: records.iter().map(|record| record.attempts).sum(),
+            total_time: records.iter().map(|record| record.time_to_answer).sum(),
+            retries: records
+                .iter()
+                .map(|record| (record.id.clone(), record.attempts.saturating_sub(1)))
+                .collect(),
+            skipped: self
+                .ids()
+                .filter(|id| !records.iter().any(|record| record.id == *id))
+                .map(str::to_owned)
+                .collect(),
+        }
+    }
+
+    /// Writes every question in order, numbered, along with its id, the type
+    /// its answer must parse as, its tags and its default hinted in
+    /// `messages`, then the sections indented under their title, without
+    /// asking anything, e.g. to preview a wizard or document it.
+    pub fn dry_run(&self, writer: &mut impl io::Write, messages: &Messages) -> io::Result<()> {
+        self.write_dry_run(writer, messages, "")
+    }
+
+    fn write_dry_run(
+        &self,
+        writer: &mut impl io::Write,
+        messages: &Messages,
+        indent: &str,
+    ) -> io::Result<()> {
+        for (number, field) in self.fields.iter().enumerate() {
+            let question = field.question(field.default.as_deref(), messages);
+
+            write!(writer, "{indent}{}. [{}] {question}", number + 1, field.id)?;
+
+            if let Some(parse) = &field.parse {
+                let constraint = messages.format(parse.constraint, &[]);
+
+                write!(
+                    writer,
+                    " {}",
+                    messages.format("dry-run-type", &[("constraint", &constraint)])
+                )?;
+            }
+
+            for tag in &field.tags {
+                write!(writer, " #{tag}")?;
+            }
+
+            writeln!(writer)?;
+        }
+
+        for section in &self.sections {
+            write!(writer, "{indent}[{}] {}", section.id, section.title)?;
+
+            if section.conditional {
+                write!(writer, " {}", messages.format("dry-run-conditional", &[]))?;
+            }
+
+            writeln!(writer)?;
+            section
+                .form
+                .write_dry_run(writer, messages, &format!("{indent}  "))?;
+        }
+
+        Ok(())
+    }
+
+    fn find(&self, id: &str) -> Option<&Field> {
+        self.fields.iter().find(|field| field.id == id)
+    }
+
+    /// Clears the screen before each question, for a full-screen wizard feel
+    /// on terminals accepting ANSI escape sequences.
+    pub fn clear_screen(mut self, clear_screen: bool) -> Self {
+        self.clear_screen = clear_screen;
+        self
+    }
+
+    /// Pins `header` at the top of the terminal while the questions scroll
+    /// beneath it, on terminals accepting ANSI escape sequences.
+    pub fn header(mut self, header: impl Into<String>) -> Self {
+        self.header = Some(header.into());
+        self
+    }
+
+    /// Remembers the answers in the file at `path`, so that the last answer
+    /// to each question becomes its default the next time the form is asked.
+    ///
+    /// The file is best-effort: failing to read or write it doesn't fail the
+    /// form.
+    pub fn remember(mut self, path: impl Into<PathBuf>) -> Self {
+        self.cache = Some(path.into());
+        self
+    }
+
+    /// Remembers the answers like [`Form::remember`], in a file named `name`
+    /// in the cache directory of `app` (e.g. `~/.cache/app/name`); nothing is
+    /// remembered when there is no cache directory.
+    pub fn remember_in_cache_dir(self, app: &str, name: &str) -> Self {
+        match cache::cache_dir(app) {
+            Some(dir) => self.remember(dir.join(name)),
+            None => self,
+        }
+    }
+
+    /// Saves the answers given so far to the file at `path` after each
+    /// question, so that asking the form again, e.g. after it was interrupted,
+    /// resumes at the first unanswered question; the file is removed once the
+    /// form is complete.
+    ///
+    /// Like [`Form::remember`], the file is best-effort.
+    pub fn resumable(mut self, path: impl Into<PathBuf>) -> Self {
+        self.state = Some(path.into());
+        self
+    }
+
+    /// Lets the `command` answer, e.g. `:undo`, discard the most recent answer
+    /// and ask its question again.
+    pub fn undo(mut self, command: impl Into<String>) -> Self {
+        self.undo = Some(command.into());
+        self
+    }
+
+    /// Lists the numbered answers once every question is answered and asks to
+    /// confirm them, asking the questions again, with the answers as defaults,
+    /// until they are; answering a number asks only that question again.
+    pub fn review(mut self, review: bool) -> Self {
+        self.review = review;
+        self
+    }
+
+    /// Answers the questions whose environment variable is set, named after
+    /// `prefix` and their id like [`Answers::export_env`] names them (e.g.
+    /// `MYAPP_DB_HOST` for `db-host` and `MYAPP_`), asking only the others.
+    pub fn env_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.env_prefix = Some(prefix.into());
+        self
+    }
+
+    /// Adds the `form` section, e.g. the database settings, asked under its
+    /// `title` once the questions are answered, whose answers are nested as
+    /// `id` (see [`Answers::section`]); the section is reusable across forms.
+    pub fn section(self, id: impl Into<String>, title: impl Into<String>, form: Form) -> Self {
+        self.push_section(id.into(), title.into(), form, Box::new(|_| true), false)
+    }
+
+    /// Adds a section like [`Form::section`], asked only when `condition`
+    /// holds for the answers given so far, e.g. when TLS was enabled.
+    pub fn section_if(
+        self,
+        id: impl Into<String>,
+        title: impl Into<String>,
+        form: Form,
+        condition: impl Fn(&Answers) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.push_section(id.into(), title.into(), form, Box::new(condition), true)
+    }
+
+    fn push_section(
+        mut self,
+        id: String,
+        title: String,
+        form: Form,
+        condition: Condition,
+        conditional: bool,
+    ) -> Self {
+        self.sections.push(Section {
+            id,
+            title,
+            form,
+            condition,
+            conditional,
+        });
+        self
+    }
+
+    pub fn ask<R: io::BufRead, W: io::Write>(
+        &self,
+        question: &mut Question<R, W>,
+    ) -> Result<Answers, QuestionError> {
+        self.ask_before(question, None)
+    }
+
+    /// Asks the questions like [`Form::ask`] within `budget` for the whole
+    /// form, sections included; once it elapsed, the remaining questions are
+    /// dealt with as `expiry` says. Waiting for an answer with a timeout takes
+    /// a [`TimeoutRead`] reader, e.g. [`crate::ThreadedReader::stdin`].
+    pub fn ask_within<R: TimeoutRead, W: io::Write>(
+        &self,
+        question: &mut Question<R, W>,
+        budget: Duration,
+        expiry: Expiry,
+    ) -> Result<Answers, QuestionError> {
+        let deadline = Deadline {
+            at: Instant::now() + budget,
+            expiry,
+            read_line: R::read_line_timeout,
+        };
+
+        self.ask_before(question, Some(&deadline))
+    }
+
+    fn ask_before<R: io::BufRead, W: io::Write>(
+        &self,
+        question: &mut Question<R, W>,
+        deadline: Option<&Deadline<R>>,
+    ) -> Result<Answers, QuestionError> {
+        let header = self.header.as_deref().filter(|_| question.decorated());
+
+        question.reset_refusals();
+
+        if let Some(header) = header {
+            pin_header(&mut question.frame, header)?;
+        }
+
+        let remembered = self.cache.as_deref().map(cache::load).unwrap_or_default();
+        let result = self
+            .ask_reviewed(question, remembered, deadline)
+            .and_then(|answers| self.ask_sections(question, answers, deadline));
+
+        if let (Some(path), Ok(answers)) = (&self.cache, &result) {
+            let _ = cache::save(path, answers);
+        }
+
+        if let (Some(path), Ok(_)) = (&self.state, &result) {
+            let _ = fs::remove_file(path);
+        }
+
+        question.leave();
+
+        if header.is_some() {
+            question.frame.extend_from_slice(b"\x1b[r");
+            question.render()?;
+        }
+
+        result
+    }
+
+    /// Asks the sections whose condition holds, nesting their answers into
+    /// `answers`.
+    fn ask_sections<R: io::BufRead, W: io::Write>(
+        &self,
+        question: &mut Question<R, W>,
+        mut answers: Answers,
+        deadline: Option<&Deadline<R>>,
+    ) -> Result<Answers, QuestionError> {
+        for section in &self.sections {
+            if !(section.condition)(&answers) {
+                continue;
+            }
+
+            match question.decorated() {
+                true => writeln!(question.frame, "\x1b[1m{}\x1b[22m", section.title)?,
+                false => writeln!(question.frame, "{}", section.title)?,
+            }
+
+            let nested = section.form.ask_before(question, deadline)?;
+
+            answers.sections.push((section.id.clone(), nested));
+        }
+
+        Ok(answers)
+    }
+
+    fn ask_reviewed<R: io::BufRead, W: io::Write>(
+        &self,
+        question: &mut Question<R, W>,
+        mut remembered: Answers,
+        deadline: Option<&Deadline<R>>,
+    ) -> Result<Answers, QuestionError> {
+        let mut resumed = self.state.as_deref().map(cache::load).unwrap_or_default();
+        let mut previous: Option<Answers> = None;
+
+        loop {
+            let mut answers = self.ask_fields(question, &remembered, &resumed, deadline)?;
+
+            if let Some(previous) = previous.take() {
+                answers.keep_records(previous);
+            }
+
+            if !self.review {
+                return Ok(answers);
+            }
+
+            match self.review_answers(question, &answers, deadline)? {
+                Review::Confirmed => return Ok(answers),
+                Review::AskAll => resumed = Answers::default(),
+                Review::Ask(index) => {
+                    resumed = answers.clone();
+                    resumed.remove(&self.fields[index].id);
+                    previous = Some(answers.clone());
+                }
+            }
+
+            remembered = answers;
+        }
+    }
+
+    /// Lists `answers` along with their numbered questions and asks to confirm
+    /// them, or which one to change.
+    fn review_answers<R: io::BufRead, W: io::Write>(
+        &self,
+        question: &mut Question<R, W>,
+        answers: &Answers,
+        deadline: Option<&Deadline<R>>,
+    ) -> Result<Review, QuestionError> {
+        let count = self.fields.len();
+        let prompt = format!(
+            "{} {}",
+            question.messages.format("review-prompt", &[]),
+            question
+                .messages
+                .format("review-hint", &[("count", &count)])
+        );
+        let yes = question.messages.list("confirm-yes");
+        let no = question.messages.list("confirm-no");
+
+        loop {
+            for (number, field) in self.fields.iter().enumerate() {
+                let value = answers.shown(&field.id, &question.messages);
+
+                writeln!(question.frame, "{}. {} {value}", number + 1, field.question)?;
+            }
+
+            let Some(answer) = ask_until(question, prompt.clone(), deadline)? else {
+                return match deadline.map(|deadline| deadline.expiry) {
+                    Some(Expiry::UseDefaults) => Ok(Review::Confirmed),
+                    _ => Err(QuestionError::DeadlineExceeded(answers.clone())),
+                };
+            };
+            let answer = answer.to_lowercase();
+
+            let review = if answer.is_empty() || yes.contains(&answer) {
+                Some(Review::Confirmed)
+            } else if no.contains(&answer) {
+                Some(Review::AskAll)
+            } else {
+                answer
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|number| number.checked_sub(1))
+                    .filter(|index| *index < count)
+                    .map(Review::Ask)
+            };
+
+            if let Some(review) = review {
+                question.accepted();
+
+                return Ok(review);
+            }
+
+            let message = question
+                .messages
+                .format("review-invalid", &[("count", &count)]);
+
+            question.write_error(&message)?;
+        }
+    }
+
+    fn ask_fields<R: io::BufRead, W: io::Write>(
+        &self,
+        question: &mut Question<R, W>,
+        remembered: &Answers,
+        resumed: &Answers,
+        deadline: Option<&Deadline<R>>,
+    ) -> Result<Answers, QuestionError> {
+        let mut answers = Answers {
+            sensitive: self
+                .fields
+                .iter()
+                .filter(|field| field.sensitive)
+                .map(|field| field.id.clone())
+                .collect(),
+            ..Answers::default()
+        };
+        let mut attempts = vec![0; self.fields.len()];
+        let mut history: Vec<usize> = Vec::new();
+        let mut position = 0;
+
+        while let Some(field) = self.fields.get(position) {
+            if let Some(answer) = self.preset(field, resumed) {
+                if answers.get(&field.id).is_none() {
+                    answers.insert(&field.id, answer);
+                }
+
+                position += 1;
+                continue;
+            }
+
+            if self.clear_screen && question.decorated() {
+                match &self.header {
+                    Some(header) => pin_header(&mut question.frame, header)?,
+                    None => question.frame.extend_from_slice(b"\x1b[2J\x1b[H"),
+                }
+            }
+
+            let default = field.default(remembered);
+            let text = field.question(default, &question.messages);
+            let asked_at = Instant::now();
+
+            let prompt = match field.sensitive {
+                true => Prompt::Secret,
+                false => Prompt::Question,
+            };
+
+            attempts[position] += 1;
+            question.enter(&field.id, &field.tags, &field.metadata);
+
+            let previous = std::mem::replace(&mut question.prompt, prompt);
+            let answer = ask_until(question, text, deadline);
+
+            question.prompt = previous;
+
+            let Some(answer) = answer? else {
+                return match deadline.map(|deadline| deadline.expiry) {
+                    Some(Expiry::UseDefaults) => {
+                        self.resolve_defaults(position, remembered, resumed, answers)
+                    }
+                    _ => Err(QuestionError::DeadlineExceeded(answers)),
+                };
+            };
+
+            if self.undo.as_deref() == Some(answer.as_str()) {
+                if let Some(previous) = history.pop() {
+                    answers.remove(&self.fields[previous].id);
+                    position = previous;
+                }
+
+                continue;
+            }
+
+            let used_default = default.is_some() && answer.is_empty();
+            let value = match default {
+                Some(default) if used_default => default.to_owned(),
+                _ => answer,
+            };
+
+            if let Some(message) = field.refusal(&value, &question.messages) {
+                question.write_error(&message)?;
+                continue;
+            }
+
+            question.accepted();
+            question.leave();
+
+            answers.records.push(AnsweredQuestion {
+                id: field.id.clone(),
+                value: match field.sensitive {
+                    true => question.messages.format("redacted", &[]),
+                    false => value.clone(),
+                },
+                answered_at: SystemTime::now(),
+                time_to_answer: asked_at.elapsed(),
+                attempts: attempts[position],
+                used_default,
+                tags: field.tags.clone(),
+                metadata: field.metadata.clone(),
+            });
+            answers.insert(&field.id, value);
+
+            if let Some(path) = &self.state {
+                let _ = cache::save(path, &answers);
+            }
+
+            history.push(position);
+            position += 1;
+        }
+
+        Ok(answers)
+    }
+
+    /// Returns the answer to `field` resumed or set in its environment
+    /// variable, which isn't asked.
+    fn preset(&self, field: &Field, resumed: &Answers) -> Option<String> {
+        resumed
+            .get(&field.id)
+            .map(str::to_owned)
+            .or_else(|| {
+                let prefix = self.env_prefix.as_deref()?;
+
+                env::var(env_name(prefix, &field.id)).ok()
+            })
+            .filter(|answer| field.accepts(answer))
+    }
+
+    /// Resolves the questions from `position` on once the deadline elapsed to
+    /// their preset answer, or else to their default, failing if one of them
+    /// has neither.
+    fn resolve_defaults(
+        &self,
+        position: usize,
+        remembered: &Answers,
+        resumed: &Answers,
+        mut answers: Answers,
+    ) -> Result<Answers, QuestionError> {
+        for field in &self.fields[position..] {
+            if answers.get(&field.id).is_some() {
+                continue;
+            }
+
+            let answer = self
+                .preset(field, resumed)
+                .or_else(|| field.default(remembered).map(str::to_owned));
+
+            match answer {
+                Some(answer) => answers.insert(&field.id, answer),
+                None => return Err(QuestionError::DeadlineExceeded(answers)),
+            }
+        }
+
+        Ok(answers)
+    }
+}
+
+impl<R: io::BufRead, W: io::Write> Question<R, W> {
+    /// Asks each `(id, question)` in turn and collects the answers by id,
+    /// stopping at EOF with `None`, for simple questionnaires not needing a
+    /// [`Form`].
+    pub fn ask_many(&mut self, questions: &[(&str, &str)]) -> io::Result<Option<Answers>> {
+        let mut answers = Answers::default();
+
+        for (id, question) in questions {
+            match self.ask(*question)? {
+                Some(answer) => answers.insert(id, answer),
+                None => return Ok(None),
+            }
+        }
+
+        Ok(Some(answers))
+    }
+}
+
+/// When a form being asked must be answered, and how to wait for an answer
+/// until then.
+struct Deadline<R> {
+    at: Instant,
+    expiry: Expiry,
+    read_line: fn(&mut R, &mut String, Duration) -> io::Result<Option<usize>>,
+}
+
+/// Reads a line, or returns `Ok(None)` if the deadline elapsed first.
+fn read_line<R: io::BufRead, W>(
+    question: &mut Question<R, W>,
+    buffer: &mut String,
+    deadline: Option<&Deadline<R>>,
+) -> io::Result<Option<usize>> {
+    match deadline {
+        Some(deadline) => {
+            let remaining = deadline.at.saturating_duration_since(Instant::now());
+
+            (deadline.read_line)(&mut question.reader, buffer, remaining)
+        }
+        None => question.reader.read_line(buffer).map(Some),
+    }
+}
+
+/// Asks `text` like [`Question::ask`], failing at EOF, or returns `Ok(None)`
+/// if the deadline elapsed first; the observer is told about the answer once
+/// accepted only.
+fn ask_until<R: io::BufRead, W: io::Write>(
+    question: &mut Question<R, W>,
+    text: String,
+    deadline: Option<&Deadline<R>>,
+) -> io::Result<Option<String>> {
+    question.write_question(text)?;
+
+    let mut buffer = String::new();
+    let Some(n) = read_line(question, &mut buffer, deadline)? else {
+        question.frame.push(b'\n');
+        question.render()?;
+        question.observer.cancelled();
+
+        return Ok(None);
+    };
+    question.observer.validating += 1;
+
+    let answer = question.answer(n, buffer);
+
+    question.observer.validating -= 1;
+
+    match answer? {
+        Some(answer) => Ok(Some(answer)),
+        None => Err(io::Error::from(io::ErrorKind::UnexpectedEof)),
+    }
+}
+
+/// Clears the screen, writes `header` on its first lines and restricts the
+/// scrolling region to the lines below.
+fn pin_header(writer: &mut impl io::Write, header: &str) -> io::Result<()> {
+    let height = header.lines().count();
+
+    write!(writer, "\x1b[r\x1b[2J\x1b[H")?;
+
+    for line in header.lines() {
+        writeln!(writer, "{line}")?;
+    }
+
+    write!(writer, "\x1b[{};r\x1b[{};1H", height + 1, height + 1)
+}
+
+/// Returns the id of the message describing what a `T` is.
+fn constraint<T>() -> &'static str {
+    match std::any::type_name::<T>() {
+        "u8" | "u16" | "u32" | "u64" | "u128" | "usize" => "constraint-natural",
+        "i8" | "i16" | "i32" | "i64" | "i128" | "isize" => "constraint-integer",
+        "f32" | "f64" => "constraint-number",
+        "bool" => "constraint-bool",
+        "char" => "constraint-char",
+        _ => "constraint-value",
+    }
+}
+
+/// Returns the name of the environment variable of the answer `id`.
+fn env_name(prefix: &str, id: &str) -> String {
+    format!("{prefix}{id}")
+        .chars()
+        .map(|c| match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' => c.to_ascii_uppercase(),
+            _ => '_',
+        })
+        .collect()
+}
+
+/// What to do once the answers of a [`Form`] were reviewed.
+enum Review {
+    Confirmed,
+    AskAll,
+    /// Ask the question at the index again.
+    Ask(usize),
+}
+
+/// The answers of a [`Form`], in the order their questions were asked.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    entries: Vec<(String, Wiped)>,
+    records: Vec<AnsweredQuestion>,
+    sensitive: Vec<String>,
+    sections: Vec<(String, Answers)>,
+}
+
+/// The statistics of a [`Form`] session, as returned by [`Form::stats`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Stats {
+    /// The number of times a question was asked.
+    pub prompts: usize,
+    /// The time spent answering the questions.
+    pub total_time: Duration,
+    /// The number of times each asked question was asked again, by id.
+    pub retries: Vec<(String, usize)>,
+    /// The ids of the questions which weren't asked, e.g. resumed ones.
+    pub skipped: Vec<String>,
+}
+
+/// How a question of a [`Form`] was answered, for analyzing how a CLI is used.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AnsweredQuestion {
+    pub id: String,
+    pub value: String,
+    pub answered_at: SystemTime,
+    pub time_to_answer: Duration,
+    /// The number of times the question was asked.
+    pub attempts: usize,
+    /// Whether the empty answer resolved to the default one.
+    pub used_default: bool,
+    /// The tags of the question, as set by [`Form::tag`].
+    pub tags: Vec<String>,
+    /// The metadata of the question, as set by [`Form::meta`].
+    pub metadata: Vec<(String, String)>,
+}
+
+impl Answers {
+    pub fn get(&self, id: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|(key, _)| key == id)
+            .map(|(_, value)| value.0.as_str())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.entries
+            .iter()
+            .map(|(id, value)| (id.as_str(), value.0.as_str()))
+    }
+
+    /// Returns the answers of the section `id`, if it was asked.
+    pub fn section(&self, id: &str) -> Option<&Answers> {
+        self.sections
+            .iter()
+            .find(|(key, _)| key == id)
+            .map(|(_, answers)| answers)
+    }
+
+    /// Returns the answers of the sections which were asked, by id.
+    pub fn sections(&self) -> impl Iterator<Item = (&str, &Answers)> {
+        self.sections
+            .iter()
+            .map(|(id, answers)| (id.as_str(), answers))
+    }
+
+    /// Tells whether the answer `id` is to a question marked as sensitive.
+    pub fn is_sensitive(&self, id: &str) -> bool {
+        self.sensitive.iter().any(|sensitive| sensitive == id)
+    }
+
+    /// Returns the answer `id` as it can be shown, i.e. replaced with the
+    /// `redacted` message in `messages` if sensitive.
+    fn shown(&self, id: &str, messages: &Messages) -> String {
+        match self.is_sensitive(id) {
+            true => messages.format("redacted", &[]),
+            false => self.get(id).unwrap_or_default().to_owned(),
+        }
+    }
+
+    /// Returns how the questions asked this time were answered, leaving out
+    /// the answers resumed or resolved to defaults once the deadline elapsed.
+    pub fn records(&self) -> &[AnsweredQuestion] {
+        &self.records
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Writes the answers as shell variable assignments which can be sourced,
+    /// e.g. `MYAPP_DB_HOST="localhost"` for the `db-host` answer and the
+    /// `MYAPP_` prefix; the sensitive answers are left out rather than given
+    /// a value, and those of the sections are prefixed with their id too, e.g.
+    /// `MYAPP_TLS_CERT`. The tags and metadata of the questions asked are
+    /// written in a comment above their assignment, e.g. `# #network`.
+    pub fn export_env(&self, writer: &mut impl io::Write, prefix: &str) -> io::Result<()> {
+        for (id, value) in self.iter().filter(|(id, _)| !self.is_sensitive(id)) {
+            let name = env_name(prefix, id);
+
+            if let Some(record) = self.records.iter().find(|record| record.id == id) {
+                let described: Vec<String> = (record.tags.iter().map(|tag| format!("#{tag}")))
+                    .chain(
+                        record
+                            .metadata
+                            .iter()
+                            .map(|(key, value)| format!("{key}={value}")),
+                    )
+                    .collect();
+
+                if !described.is_empty() {
+                    writeln!(
+                        writer,
+                        "# {}",
+                        described.join(" ").replace(['\n', '\r'], " ")
+                    )?;
+                }
+            }
+
+            let mut quoted = String::with_capacity(value.len());
+
+            for c in value.chars() {
+                if matches!(c, '"' | '\\' | '$' | '`') {
+                    quoted.push('\\');
+                }
+
+                quoted.push(c);
+            }
+
+            writeln!(writer, "{name}=\"{quoted}\"")?;
+        }
+
+        for (id, answers) in self.sections() {
+            answers.export_env(writer, &format!("{prefix}{id}_"))?;
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn insert(&mut self, id: &str, value: String) {
+        self.entries.push((id.to_owned(), Wiped(value)));
+    }
+
+    /// Keeps the records of `previous` for the questions which weren't asked
+    /// again, adding up the attempts of those which were.
+    fn keep_records(&mut self, previous: Answers) {
+        let mut records = previous.records;
+
+        for record in self.records.drain(..) {
+            match records.iter_mut().find(|previous| previous.id == record.id) {
+                Some(previous) => {
+                    *previous = AnsweredQuestion {
+                        attempts: previous.attempts + record.attempts,
+                        ..record
+                    }
+                }
+                None => records.push(record),
+            }
+        }
+
+        self.records = records;
+    }
+
+    fn remove(&mut self, id: &str) {
+        self.entries.retain(|(key, _)| key != id);
+        self.records.retain(|record| record.id != id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timeout::tests::Silent;
+
+    fn form() -> Form {
+        Form::new()
+            .field("name", "Your name?")
+            .field_with_default("port", "Port?", "8080")
+    }
+
+    #[test]
+    fn ask_should_collect_the_answers_by_id() {
+        let answers = form()
+            .ask(&mut Question::new("Alice\n4242\n".as_bytes(), io::sink()))
+            .expect("ask() should not fail");
+
+        assert_eq!(answers.get("name"), Some("Alice"));
+        assert_eq!(answers.get("port"), Some("4242"));
+    }
+
+    #[test]
+    fn ask_should_read_any_buffered_reader() {
+        let reader = io::BufReader::new(io::Cursor::new("Alice\n4242\n"));
+        let answers = form()
+            .ask(&mut Question::new(reader, io::sink()))
+            .expect("ask() should not fail");
+
+        assert_eq!(answers.get("name"), Some("Alice"));
+        assert_eq!(answers.get("port"), Some("4242"));
+    }
+
+    #[test]
+    fn ask_should_use_the_default_for_an_empty_answer() {
+        let mut output = Vec::new();
+        let answers = form()
+            .ask(&mut Question::new("Alice\n\n".as_bytes(), &mut output))
+            .expect("ask() should not fail");
+        let output = String::from_utf8(output).expect("from_utf8() should not fail");
+
+        assert_eq!(answers.get("port"), Some("8080"));
+        assert!(output.ends_with("Port? (default: 8080) "));
+    }
+
+    #[test]
+    fn ask_should_record_how_each_question_was_answered() {
+        let answers = form()
+            .ask(&mut Question::new("Alice\n\n".as_bytes(), io::sink()))
+            .expect("ask() should not fail");
+        let records: Vec<_> = answers
+            .records()
+            .iter()
+            .map(|record| (record.id.as_str(), record.attempts, record.used_default))
+            .collect();
+
+        assert_eq!(records, [("name", 1, false), ("port", 1, true)]);
+    }
+
+    #[test]
+    fn stats_should_sum_up_the_answers() {
+        let answers = form()
+            .ask(&mut Question::new("Alice\n4242\n".as_bytes(), io::sink()))
+            .expect("ask() should not fail");
+        let stats = form().field("age", "Age?").stats(&answers);
+
+        assert_eq!(stats.prompts, 2);
+        assert_eq!(
+            stats.retries,
+            [(String::from("name"), 0), (String::from("port"), 0)]
+        );
+        assert_eq!(stats.skipped, ["age"]);
+    }
+
+    #[test]
+    fn ask_should_ask_the_previous_question_again_on_undo() {
+        let mut output = Vec::new();
+        let answers = form()
+            .undo(":undo")
+            .ask(&mut Question::new(
+                "Alcie\n:undo\nAlice\n4242\n".as_bytes(),
+                &mut output,
+            ))
+            .expect("ask() should not fail");
+
+        assert_eq!(answers.get("name"), Some("Alice"));
+        assert_eq!(answers.get("port"), Some("4242"));
+        assert_eq!(answers.records()[0].attempts, 2);
+        assert_eq!(
+            output,
+            b"Your name? Port? (default: 8080) Your name? Port? (default: 8080) "
+        );
+    }
+
+    #[test]
+    fn ask_should_ask_again_until_the_answers_are_confirmed() {
+        let mut output = Vec::new();
+        let answers = form()
+            .review(true)
+            .ask(&mut Question::new(
+                "Alcie\n\nn\nAlice\n\n\n".as_bytes(),
+                &mut output,
+            ))
+            .expect("ask() should not fail");
+        let output = String::from_utf8(output).expect("from_utf8() should not fail");
+
+        assert_eq!(answers.get("name"), Some("Alice"));
+        assert!(output.starts_with(
+            "Your name? Port? (default: 8080) 1. Your name? Alcie\n2. Port? 8080\n\
+             Does this look right? [Y/n/1-2] "
+        ));
+        assert!(output.contains("Your name? (default: Alcie) "));
+    }
+
+    #[test]
+    fn ask_should_ask_a_reviewed_question_again_by_number() {
+        let mut output = Vec::new();
+        let answers = form()
+            .review(true)
+            .ask(&mut Question::new(
+                "Alice\n\n2\n4242\ny\n".as_bytes(),
+                &mut output,
+            ))
+            .expect("ask() should not fail");
+        let output = String::from_utf8(output).expect("from_utf8() should not fail");
+
+        assert_eq!(answers.get("name"), Some("Alice"));
+        assert_eq!(answers.get("port"), Some("4242"));
+        assert_eq!(answers.records()[1].attempts, 2);
+        assert!(output.ends_with(
+            "Port? (default: 8080) 1. Your name? Alice\n2. Port? 4242\n\
+             Does this look right? [Y/n/1-2] "
+        ));
+    }
+
+    #[test]
+    fn ask_should_redact_the_sensitive_answers() {
+        let path = std::env::temp_dir().join(format!("question-{}.secret", std::process::id()));
+        let mut output = Vec::new();
+        let answers = Form::new()
+            .field("name", "Your name?")
+            .field("token", "Token?")
+            .sensitive()
+            .remember(&path)
+            .review(true)
+            .ask(&mut Question::new(
+                "Alice\nhunter2\n\n".as_bytes(),
+                &mut output,
+            ))
+            .expect("ask() should not fail");
+        let output = String::from_utf8(output).expect("from_utf8() should not fail");
+        let mut exported = Vec::new();
+
+        answers
+            .export_env(&mut exported, "")
+            .expect("export_env() should not fail");
+
+        assert_eq!(answers.get("token"), Some("hunter2"));
+        assert_eq!(answers.records()[1].value, "«redacted»");
+        assert!(output.contains("2. Token? «redacted»\n"));
+        assert_eq!(exported, b"NAME=\"Alice\"\n");
+        assert_eq!(cache::load(&path).get("token"), None);
+
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn ask_should_mask_the_sensitive_defaults_once_reviewed() {
+        let mut output = Vec::new();
+        let answers = Form::new()
+            .field("token", "Token?")
+            .sensitive()
+            .review(true)
+            .ask(&mut Question::new(
+                "hunter2\nn\n\n\n".as_bytes(),
+                &mut output,
+            ))
+            .expect("ask() should not fail");
+        let output = String::from_utf8(output).expect("from_utf8() should not fail");
+
+        assert_eq!(answers.get("token"), Some("hunter2"));
+        assert!(output.contains("Token? (default: «redacted») "));
+        assert!(!output.contains("hunter2"));
+    }
+
+    #[test]
+    fn ask_should_echo_the_answers_in_ansi_mode() {
+        let mut output = Vec::new();
+
+        Form::new()
+            .field("name", "Your name?")
+            .field("token", "Token?")
+            .sensitive()
+            .ask(&mut Question::new("Alice\nhunter2\n".as_bytes(), &mut output).ansi(true))
+            .expect("ask() should not fail");
+        let output = String::from_utf8(output).expect("from_utf8() should not fail");
+
+        assert!(output.contains("\x1b[32m✔\x1b[39m Your name? … \x1b[36mAlice\x1b[39m\n"));
+        assert!(output.ends_with("\x1b[32m✔\x1b[39m Token? … \x1b[36m••••••••\x1b[39m\n"));
+    }
+
+    #[test]
+    fn ask_should_erase_the_answered_questions_when_transient() {
+        let mut output = Vec::new();
+
+        form()
+            .ask(
+                &mut Question::new("Alice\n\n".as_bytes(), &mut output)
+                    .ansi(true)
+                    .transient(true),
+            )
+            .expect("ask() should not fail");
+
+        assert_eq!(
+            output,
+            b"Your name? \x1b[1A\r\x1b[JPort? (default: 8080) \x1b[1A\r\x1b[J"
+        );
+    }
+
+    #[test]
+    fn ask_should_ask_again_until_the_answer_parses() {
+        let mut output = Vec::new();
+        let answers = Form::new()
+            .field("port", "Port?")
+            .parsed_with_message::<u16>("'{value}' is not {constraint}.")
+            .ask(&mut Question::new("http\n8080\n".as_bytes(), &mut output))
+            .expect("ask() should not fail");
+
+        assert_eq!(answers.get("port"), Some("8080"));
+        assert_eq!(answers.records()[0].attempts, 2);
+        assert_eq!(
+            output,
+            "Port? 'http' is not a non-negative whole number.\nPort? ".as_bytes()
+        );
+    }
+
+    #[test]
+    fn dry_run_should_write_every_question_without_asking() {
+        let mut output = Vec::new();
+
+        form()
+            .parsed::<u16>()
+            .tag("network")
+            .section("db", "Database settings", form())
+            .section_if("tls", "TLS settings", tls(), |_| false)
+            .dry_run(
+                &mut output,
+                &Messages::default().set("default-hint", "(défaut : { $default })"),
+            )
+            .expect("dry_run() should not fail");
+
+        assert_eq!(
+            String::from_utf8(output).expect("from_utf8() should not fail"),
+            "1. [name] Your name?\n\
+             2. [port] Port? (défaut : 8080) <a non-negative whole number> #network\n\
+             [db] Database settings\n\
+             \x20 1. [name] Your name?\n\
+             \x20 2. [port] Port? (défaut : 8080)\n\
+             [tls] TLS settings (if its condition holds)\n\
+             \x20 1. [cert] Certificate?\n\
+             \x20 2. [key] Key?\n"
+        );
+    }
+
+    fn tls() -> Form {
+        Form::new()
+            .field("cert", "Certificate?")
+            .field("key", "Key?")
+    }
+
+    #[test]
+    fn ask_should_nest_the_answers_of_the_sections() {
+        let mut output = Vec::new();
+        let answers = Form::new()
+            .field("tls", "Enable TLS?")
+            .section("db", "Database settings", form())
+            .section_if("tls", "TLS settings", tls(), |answers| {
+                answers.get("tls") == Some("y")
+            })
+            .ask(&mut Question::new(
+                "y\nAlice\n\ncert.pem\nkey.pem\n".as_bytes(),
+                &mut output,
+            ))
+            .expect("ask() should not fail");
+        let mut exported = Vec::new();
+
+        answers
+            .export_env(&mut exported, "APP_")
+            .expect("export_env() should not fail");
+
+        assert_eq!(
+            answers.section("db").and_then(|db| db.get("port")),
+            Some("8080")
+        );
+        assert_eq!(
+            String::from_utf8(output).expect("from_utf8() should not fail"),
+            "Enable TLS? Database settings\nYour name? Port? (default: 8080) \
+             TLS settings\nCertificate? Key? "
+        );
+        assert_eq!(
+            String::from_utf8(exported).expect("from_utf8() should not fail"),
+            "APP_TLS=\"y\"\nAPP_DB_NAME=\"Alice\"\nAPP_DB_PORT=\"8080\"\n\
+             APP_TLS_CERT=\"cert.pem\"\nAPP_TLS_KEY=\"key.pem\"\n"
+        );
+    }
+
+    #[test]
+    fn ask_should_skip_the_sections_whose_condition_does_not_hold() {
+        let answers = Form::new()
+            .field("tls", "Enable TLS?")
+            .section_if("tls", "TLS settings", tls(), |answers| {
+                answers.get("tls") == Some("y")
+            })
+            .ask(&mut Question::new("n\n".as_bytes(), io::sink()))
+            .expect("ask() should not fail");
+
+        assert!(answers.section("tls").is_none());
+    }
+
+    #[test]
+    fn ask_should_take_the_answers_set_in_the_environment() {
+        env::set_var("QUESTION_TEST_PORT", "4242");
+
+        let mut output = Vec::new();
+        let answers = form()
+            .env_prefix("question-test-")
+            .ask(&mut Question::new("Alice\n".as_bytes(), &mut output))
+            .expect("ask() should not fail");
+
+        assert_eq!(answers.get("port"), Some("4242"));
+        assert_eq!(output, b"Your name? ");
+    }
+
+    #[test]
+    fn ask_should_replace_the_refused_attempt_in_ansi_mode() {
+        let mut output = Vec::new();
+
+        Form::new()
+            .field("port", "Port?")
+            .parsed::<u16>()
+            .ask(
+                &mut Question::new("http\nhttp\n80\n".as_bytes(), &mut output)
+                    .ansi(true)
+                    .echo_answers(false),
+            )
+            .expect("ask() should not fail");
+        let output = String::from_utf8(output).expect("from_utf8() should not fail");
+
+        assert_eq!(
+            output,
+            "Port? \x1b[1A\r\x1b[J'http' is not a valid answer.\n\
+             Port? \x1b[2A\r\x1b[J'http' is not a valid answer.\nPort? "
+        );
+    }
+
+    #[test]
+    fn ask_should_abort_after_too_many_refused_answers() {
+        let error = Form::new()
+            .field("port", "Port?")
+            .parsed::<u16>()
+            .ask(
+                &mut Question::new("http\nhttp\n80\n".as_bytes(), io::sink())
+                    .lockout(crate::Lockout::Abort { after: 2 }),
+            )
+            .expect_err("ask() should fail");
+
+        assert!(matches!(error, QuestionError::LockedOut));
+    }
+
+    #[test]
+    fn tagged_should_return_the_ids_of_the_tagged_questions() {
+        let form = form()
+            .tag("advanced")
+            .meta("section", "network")
+            .field("token", "Token?")
+            .tag("advanced")
+            .tag("secret");
+
+        assert_eq!(
+            form.tagged("advanced").collect::<Vec<_>>(),
+            ["port", "token"]
+        );
+        assert_eq!(form.tags("token"), ["advanced", "secret"]);
+        assert_eq!(form.metadata("port", "section"), Some("network"));
+        assert_eq!(form.metadata("name", "section"), None);
+    }
+
+    #[test]
+    fn ask_should_record_the_tags_and_metadata_of_the_questions() {
+        let answers = form()
+            .tag("advanced")
+            .meta("section", "network")
+            .ask(&mut Question::new("Alice\n80\n".as_bytes(), io::sink()))
+            .expect("ask() should not fail");
+        let records = answers.records();
+
+        assert!(records[0].tags.is_empty());
+        assert_eq!(records[1].tags, ["advanced"]);
+        assert_eq!(
+            records[1].metadata,
+            [(String::from("section"), String::from("network"))]
+        );
+    }
+
+    #[test]
+    fn ask_should_clear_the_screen_before_each_question() {
+        let mut output = Vec::new();
+
+        form()
+            .clear_screen(true)
+            .ask(
+                &mut Question::new("Alice\n\n".as_bytes(), &mut output)
+                    .ansi(true)
+                    .echo_answers(false),
+            )
+            .expect("ask() should not fail");
+        let output = String::from_utf8(output).expect("from_utf8() should not fail");
+
+        assert_eq!(
+            output,
+            "\x1b[2J\x1b[HYour name? \x1b[2J\x1b[HPort? (default: 8080) "
+        );
+    }
+
+    #[test]
+    fn ask_should_not_clear_the_screen_without_ansi() {
+        let mut output = Vec::new();
+
+        form()
+            .clear_screen(true)
+            .ask(&mut Question::new("Alice\n\n".as_bytes(), &mut output))
+            .expect("ask() should not fail");
+
+        assert!(!output.contains(&b'\x1b'));
+    }
+
+    #[test]
+    fn ask_should_pin_the_header_above_the_questions() {
+        let mut output = Vec::new();
+
+        Form::new()
+            .field("name", "Your name?")
+            .header("My app\nStep 1")
+            .ask(
+                &mut Question::new("Alice\n".as_bytes(), &mut output)
+                    .ansi(true)
+                    .echo_answers(false),
+            )
+            .expect("ask() should not fail");
+        let output = String::from_utf8(output).expect("from_utf8() should not fail");
+
+        assert_eq!(
+            output,
+            "\x1b[r\x1b[2J\x1b[HMy app\nStep 1\n\x1b[3;r\x1b[3;1HYour name? \x1b[r"
+        );
+    }
+
+    #[test]
+    fn ask_should_resolve_the_remaining_questions_to_their_defaults_past_the_deadline() {
+        let answers = Form::new()
+            .field_with_default("region", "Region?", "eu-west-1")
+            .field_with_default("port", "Port?", "8080")
+            .ask_within(
+                &mut Question::new(Silent, io::sink()),
+                Duration::ZERO,
+                Expiry::UseDefaults,
+            )
+            .expect("ask() should not fail");
+
+        assert_eq!(answers.get("region"), Some("eu-west-1"));
+        assert_eq!(answers.get("port"), Some("8080"));
+    }
+
+    #[test]
+    fn ask_should_resolve_the_remaining_questions_to_their_presets_past_the_deadline() {
+        env::set_var("QUESTION_DEADLINE_TOKEN", "hunter2");
+
+        let answers = Form::new()
+            .field_with_default("region", "Region?", "eu-west-1")
+            .field("token", "Token?")
+            .env_prefix("question-deadline-")
+            .ask_within(
+                &mut Question::new(Silent, io::sink()),
+                Duration::ZERO,
+                Expiry::UseDefaults,
+            )
+            .expect("ask() should not fail");
+
+        assert_eq!(answers.get("region"), Some("eu-west-1"));
+        assert_eq!(answers.get("token"), Some("hunter2"));
+    }
+
+    #[test]
+    fn ask_should_confirm_the_review_past_the_deadline() {
+        let answers = Form::new()
+            .field_with_default("region", "Region?", "eu-west-1")
+            .review(true)
+            .ask_within(
+                &mut Question::new(Silent, io::sink()),
+                Duration::ZERO,
+                Expiry::UseDefaults,
+            )
+            .expect("ask() should not fail");
+
+        assert_eq!(answers.get("region"), Some("eu-west-1"));
+    }
+
+    #[test]
+    fn ask_should_abort_with_the_partial_answers_past_the_deadline() {
+        let error = form()
+            .ask_within(
+                &mut Question::new(Silent, io::sink()),
+                Duration::ZERO,
+                Expiry::Abort,
+            )
+            .expect_err("ask() should fail");
+
+        assert!(matches!(error, QuestionError::DeadlineExceeded(answers) if answers.is_empty()));
+    }
+
+    #[test]
+    fn ask_should_abort_past_the_deadline_when_a_question_has_no_default() {
+        let error = form()
+            .ask_within(
+                &mut Question::new(Silent, io::sink()),
+                Duration::ZERO,
+                Expiry::UseDefaults,
+            )
+            .expect_err("ask() should fail");
+
+        assert!(matches!(error, QuestionError::DeadlineExceeded(_)));
+    }
+
+    #[test]
+    fn ask_should_use_the_remembered_answers_as_defaults() {
+        let path = std::env::temp_dir().join(format!("question-{}.cache", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        form()
+            .remember(&path)
+            .ask(&mut Question::new("Alice\n4242\n".as_bytes(), io::sink()))
+            .expect("ask() should not fail");
+
+        let mut output = Vec::new();
+        let answers = form()
+            .remember(&path)
+            .ask(&mut Question::new("\n\n".as_bytes(), &mut output))
+            .expect("ask() should not fail");
+        let output = String::from_utf8(output).expect("from_utf8() should not fail");
+
+        std::fs::remove_file(path).expect("remove_file() should not fail");
+        assert_eq!(answers.get("name"), Some("Alice"));
+        assert_eq!(answers.get("port"), Some("4242"));
+        assert_eq!(output, "Your name? (default: Alice) Port? (default: 4242) ");
+    }
+
+    #[test]
+    fn ask_should_resume_at_the_first_unanswered_question() {
+        let path = std::env::temp_dir().join(format!("question-{}.state", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        form()
+            .resumable(&path)
+            .ask(&mut Question::new("Alice\n".as_bytes(), io::sink()))
+            .expect_err("ask() should fail");
+
+        let mut output = Vec::new();
+        let answers = form()
+            .resumable(&path)
+            .ask(&mut Question::new("4242\n".as_bytes(), &mut output))
+            .expect("ask() should not fail");
+
+        assert_eq!(answers.get("name"), Some("Alice"));
+        assert_eq!(answers.get("port"), Some("4242"));
+        assert_eq!(output, b"Port? (default: 8080) ");
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn ask_many_should_collect_the_answers_by_id() {
+        let answers = Question::new("Alice\nalice@example.com\n".as_bytes(), io::sink())
+            .ask_many(&[("name", "Your name?"), ("email", "Email?")])
+            .expect("ask_many() should not fail")
+            .expect("ask_many() should return the answers");
+
+        assert_eq!(answers.get("name"), Some("Alice"));
+        assert_eq!(answers.get("email"), Some("alice@example.com"));
+    }
+
+    #[test]
+    fn ask_many_should_stop_at_eof() {
+        let mut output = Vec::new();
+        let answers = Question::new("Alice\n".as_bytes(), &mut output)
+            .ask_many(&[("name", "Your name?"), ("email", "Email?"), ("age", "Age?")])
+            .expect("ask_many() should not fail");
+
+        assert!(answers.is_none());
+        assert_eq!(output, b"Your name? Email? ");
+    }
+
+    #[test]
+    fn export_env_should_describe_the_tags_and_metadata_in_comments() {
+        let answers = Form::new()
+            .field("port", "Port?")
+            .tag("network")
+            .meta("section", "server\nrm -rf")
+            .field("name", "Your name?")
+            .ask(&mut Question::new("80\nAlice\n".as_bytes(), io::sink()))
+            .expect("ask() should not fail");
+        let mut output = Vec::new();
+
+        answers
+            .export_env(&mut output, "")
+            .expect("export_env() should not fail");
+
+        assert_eq!(
+            String::from_utf8(output).expect("from_utf8() should not fail"),
+            "# #network section=server rm -rf\nPORT=\"80\"\nNAME=\"Alice\"\n"
+        );
+    }
+
+    #[test]
+    fn export_env_should_write_quoted_shell_assignments() {
+        let mut output = Vec::new();
+        let mut answers = Answers::default();
+
+        answers.insert("db-host", String::from("localhost"));
+        answers.insert("motd", String::from(r#"Say "hi" to $USER `now` \o/"#));
+        answers
+            .export_env(&mut output, "myapp_")
+            .expect("export_env() should not fail");
+        let output = String::from_utf8(output).expect("from_utf8() should not fail");
+
+        assert_eq!(
+            output,
+            "MYAPP_DB_HOST=\"localhost\"\n\
+             MYAPP_MOTD=\"Say \\\"hi\\\" to \\$USER \\`now\\` \\\\o/\"\n"
+        );
+    }
+}
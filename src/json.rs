@@ -0,0 +1,279 @@
+use crate::{BackendQuestion, PromptBackend, Question};
+use std::{
+    io::{self, BufRead, Write},
+    mem,
+};
+
+/// A [`PromptBackend`] speaking a machine-readable protocol, for programs such
+/// as GUIs or editor extensions to drive the questions.
+///
+/// Everything displayed is written as a JSON object on its own line, like
+/// `{"type":"question","text":"Your name? "}` when an answer is expected or
+/// `{"type":"output","text":"..."}` otherwise. Each answer is read as a JSON
+/// string on its own line, which mustn't hold a line break, `null` meaning
+/// that there is no answer.
+pub struct JsonLines<R, W: Write> {
+    reader: R,
+    writer: W,
+    /// The frames rendered since the last answer, not emitted yet.
+    frames: Vec<String>,
+}
+
+impl<R: BufRead, W: Write> JsonLines<R, W> {
+    pub fn new(reader: R, writer: W) -> Self {
+        Self {
+            reader,
+            writer,
+            frames: Vec::new(),
+        }
+    }
+}
+
+impl<R, W: Write> JsonLines<R, W> {
+    /// Emits the pending frames, the last one being a question when `question`
+    /// is set.
+    fn emit(&mut self, question: bool) -> io::Result<()> {
+        let frames = mem::take(&mut self.frames);
+        let last = frames.len().saturating_sub(1);
+
+        for (index, text) in frames.iter().enumerate() {
+            let kind = if question && index == last {
+                "question"
+            } else {
+                "output"
+            };
+
+            writeln!(
+                self.writer,
+                r#"{{"type":"{kind}","text":"{}"}}"#,
+                escape(text)
+            )?;
+        }
+
+        self.writer.flush()
+    }
+}
+
+impl<R: BufRead, W: Write> PromptBackend for JsonLines<R, W> {
+    fn render(&mut self, frame: &[u8]) -> io::Result<()> {
+        self.frames
+            .push(String::from_utf8_lossy(frame).into_owned());
+        Ok(())
+    }
+
+    fn read_line(&mut self, line: &mut String) -> io::Result<usize> {
+        self.emit(true)?;
+
+        let mut json = String::new();
+
+        if self.reader.read_line(&mut json)? == 0 || json.trim() == "null" {
+            return Ok(0);
+        }
+
+        let answer = parse_string(json.trim()).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                "the answer is not a JSON string",
+            )
+        })?;
+
+        if answer.contains('\n') {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "the answer holds a line break",
+            ));
+        }
+
+        line.push_str(&answer);
+        line.push('\n');
+
+        Ok(answer.len() + 1)
+    }
+}
+
+impl<R, W: Write> Drop for JsonLines<R, W> {
+    fn drop(&mut self) {
+        let _ = self.emit(false);
+    }
+}
+
+impl<R: BufRead, W: Write> BackendQuestion<JsonLines<R, W>> {
+    /// Asks questions through the [`JsonLines`] protocol.
+    pub fn json_lines(reader: R, writer: W) -> Self {
+        Question::with_backend(JsonLines::new(reader, writer))
+    }
+}
+
+/// Escapes `text` to be written inside a JSON string.
+pub(crate) fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c < ' ' => escaped.push_str(&format!("\\u{:04x}", u32::from(c))),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+/// Parses a JSON string such as `"Alice"`.
+pub(crate) fn parse_string(json: &str) -> Option<String> {
+    let mut chars = json.strip_prefix('"')?.chars();
+    let mut parsed = String::new();
+
+    loop {
+        match chars.next()? {
+            '"' => return chars.as_str().is_empty().then_some(parsed),
+            '\\' => match chars.next()? {
+                '"' => parsed.push('"'),
+                '\\' => parsed.push('\\'),
+                '/' => parsed.push('/'),
+                'b' => parsed.push('\u{8}'),
+                'f' => parsed.push('\u{c}'),
+                'n' => parsed.push('\n'),
+                'r' => parsed.push('\r'),
+                't' => parsed.push('\t'),
+                'u' => {
+                    let high = parse_hex(&mut chars)?;
+                    let code = if (0xd800..0xdc00).contains(&high) {
+                        if chars.next()? != '\\' || chars.next()? != 'u' {
+                            return None;
+                        }
+
+                        let low =
+                            parse_hex(&mut chars).filter(|low| (0xdc00..0xe000).contains(low))?;
+
+                        0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00)
+                    } else {
+                        high
+                    };
+
+                    parsed.push(char::from_u32(code)?);
+                }
+                _ => return None,
+            },
+            c if c < ' ' => return None,
+            c => parsed.push(c),
+        }
+    }
+}
+
+/// Parses the 4 hexadecimal digits of a `\u` escape sequence, refusing the
+/// sign `from_str_radix` would accept.
+fn parse_hex(chars: &mut std::str::Chars<'_>) -> Option<u32> {
+    let hex: String = chars.take(4).collect();
+
+    if hex.len() == 4 && hex.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        u32::from_str_radix(&hex, 16).ok()
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn json_lines(
+        input: &str,
+        f: impl FnOnce(&mut BackendQuestion<JsonLines<&[u8], &mut Vec<u8>>>),
+    ) -> String {
+        let mut output = Vec::new();
+        let mut question = Question::json_lines(input.as_bytes(), &mut output);
+
+        f(&mut question);
+        drop(question);
+
+        String::from_utf8(output).expect("from_utf8() should not fail")
+    }
+
+    #[test]
+    fn ask_should_emit_the_question_and_decode_the_answer() {
+        let mut result = None;
+        let output = json_lines("\"fine \\\"thanks\\\"\"\n", |question| {
+            result = question.ask("How are you?").expect("ask() should not fail");
+        });
+
+        assert_eq!(result, Some(String::from("fine \"thanks\"")));
+        assert_eq!(
+            output,
+            "{\"type\":\"question\",\"text\":\"How are you? \"}\n"
+        );
+    }
+
+    #[test]
+    fn ask_should_return_none_for_a_null_answer() {
+        let mut result = Some(String::new());
+
+        json_lines("null\n", |question| {
+            result = question.ask("How are you?").expect("ask() should not fail");
+        });
+
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn ask_should_fail_for_an_answer_which_is_not_a_json_string() {
+        json_lines("fine\n", |question| {
+            let error = question.ask("How are you?").expect_err("ask() should fail");
+
+            assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        });
+    }
+
+    #[test]
+    fn ask_should_fail_for_an_answer_holding_a_line_break() {
+        json_lines("\"fine\\nyes\"\n", |question| {
+            let error = question.ask("How are you?").expect_err("ask() should fail");
+
+            assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        });
+    }
+
+    #[test]
+    fn confirm_should_emit_the_error_before_asking_again() {
+        let output = json_lines("\"maybe\"\n\"yes\"\n", |question| {
+            question
+                .confirm("Sure?", true)
+                .expect("confirm() should not fail");
+        });
+
+        assert_eq!(
+            output,
+            "{\"type\":\"question\",\"text\":\"Sure? [Y/n] \"}\n\
+             {\"type\":\"question\",\"text\":\"Please answer yes or no.\\nSure? [Y/n] \"}\n"
+        );
+    }
+
+    #[test]
+    fn escape_should_escape_quotes_backslashes_and_control_characters() {
+        assert_eq!(
+            escape("a \"b\" \\ c\n\u{1b}"),
+            "a \\\"b\\\" \\\\ c\\n\\u001b"
+        );
+    }
+
+    #[test]
+    fn parse_string_should_decode_the_escape_sequences() {
+        assert_eq!(
+            parse_string(r#""a\"b\\c\/\né😀""#),
+            Some(String::from("a\"b\\c/\né😀"))
+        );
+    }
+
+    #[test]
+    fn parse_string_should_reject_invalid_json_strings() {
+        assert_eq!(parse_string("\"unterminated"), None);
+        assert_eq!(parse_string("\"trailing\" data"), None);
+        assert_eq!(parse_string("unquoted"), None);
+        assert_eq!(parse_string(r#""\ud83d\ue000""#), None);
+        assert_eq!(parse_string(r#""\u+041""#), None);
+    }
+}
@@ -1,35 +1,1151 @@
-use std::io;
+mod backend;
+mod cache;
+#[cfg(feature = "select")]
+mod choice;
+mod color;
+mod command;
+mod console;
+mod conversion;
+mod definition;
+mod error;
+mod form;
+mod json;
+mod macros;
+mod markdown;
+mod matching;
+mod messages;
+mod mock;
+mod observer;
+mod overwrite;
+mod queue;
+mod recorder;
+mod secret;
+#[cfg(feature = "select")]
+mod select;
+mod shared;
+mod stepper;
+mod theme;
+mod timeout;
+#[cfg(feature = "validators")]
+pub mod validators;
+
+use std::{
+    env, fmt,
+    io::{self, IsTerminal, Write},
+    ops::Range,
+    str::FromStr,
+    thread,
+    time::Duration,
+};
+
+#[cfg(feature = "tty")]
+use std::fs;
+
+#[cfg(unix)]
+use std::os::unix::net::{UnixListener, UnixStream};
+
+pub use backend::{BackendQuestion, BackendReader, BackendWriter, PromptBackend, Streams};
+#[cfg(feature = "select")]
+pub use choice::Choice;
+pub use color::ParseColorError;
+pub use console::{Expectation, TestConsole};
+pub use conversion::Conversion;
+pub use error::QuestionError;
+pub use form::{AnsweredQuestion, Answers, Expiry, Form, Stats};
+pub use json::JsonLines;
+pub use messages::Messages;
+pub use mock::MockBackend;
+pub use observer::{PromptObserver, QuestionContext};
+pub use overwrite::Overwrite;
+pub use queue::QuestionQueue;
+pub use secret::Lockout;
+#[cfg(feature = "select")]
+pub use select::Selection;
+pub use shared::SharedQuestion;
+pub use theme::{Bell, Color, ColorDepth, Icons, Theme};
+pub use timeout::{ThreadedReader, TimeoutRead};
+
+use matching::Matching;
+use observer::Observer;
+use recorder::Recorder;
+use theme::Prompt;
+
+const DEFAULT_WIDTH: usize = 80;
+/// How long the screen flashes for the visual bell.
+const FLASH: Duration = Duration::from_millis(100);
+/// What replaces the secret answers once echoed, not telling their length;
+/// they are replaced with the `redacted` message elsewhere, e.g. once
+/// recorded or reviewed.
+const SECRET_MASK: &str = "••••••••";
 
 pub struct Question<R, W> {
     reader: R,
     writer: W,
+    /// What is about to be written, so that it reaches the writer at once.
+    frame: Vec<u8>,
+    theme: Theme,
+    messages: Messages,
+    ansi: bool,
+    width: usize,
+    interactive: bool,
+    accessible: bool,
+    skip_token: String,
+    blank_is_empty: bool,
+    matching: Matching,
+    observer: Observer,
+    color_depth: ColorDepth,
+    hyperlinks: bool,
+    markdown: bool,
+    lockout: Option<Lockout>,
+    prompt: Prompt,
+    echo: bool,
+    shown: String,
+    echoed: Option<String>,
+    transient: bool,
+    /// The rows taken on the screen by the question last asked along with its
+    /// answer, none once erased.
+    rows: usize,
+    /// The rows taken on the screen by the last refusal of the question being
+    /// asked again, above it.
+    error_rows: usize,
+    /// The refusals of the question being asked again, as counted by the
+    /// lockout policy.
+    failures: usize,
+    /// The phrases mistyped in a row, as counted by the lockout policy.
+    mistyped_phrases: usize,
+    recorder: Option<Recorder>,
+}
+
+/// The answer to an optional question.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    Given(String),
+    /// The question was skipped by answering the skip token.
+    Skipped,
 }
 
-impl<'a> Default for Question<io::StdinLock<'a>, io::StdoutLock<'a>> {
+impl Default for Question<io::StdinLock<'static>, io::StdoutLock<'static>> {
     fn default() -> Self {
-        Question::new(io::stdin().lock(), io::stdout().lock())
+        let terminal = io::stdout().is_terminal();
+
+        Question::new(io::stdin().lock(), io::stdout().lock()).detect_terminal(terminal)
+    }
+}
+
+impl Question<io::StdinLock<'static>, io::Stdout> {
+    /// Asks questions on the standard streams with owned handles, which can be
+    /// stored anywhere; standard output is only locked while being written.
+    pub fn stdio() -> Self {
+        let terminal = io::stdout().is_terminal();
+
+        Question::new(io::stdin().lock(), io::stdout()).detect_terminal(terminal)
+    }
+}
+
+impl Question<io::StdinLock<'static>, io::Stderr> {
+    /// Asks questions on the standard error, keeping the standard output clean
+    /// for the program's results, e.g. when piped into another program.
+    pub fn stderr() -> Self {
+        let terminal = io::stderr().is_terminal();
+
+        Question::new(io::stdin().lock(), io::stderr()).detect_terminal(terminal)
+    }
+}
+
+#[cfg(feature = "tty")]
+impl Question<io::BufReader<fs::File>, fs::File> {
+    /// Asks questions on the controlling terminal (`/dev/tty`, or `CONIN$` and
+    /// `CONOUT$` on Windows), which works even when both the standard input
+    /// and output are redirected.
+    pub fn tty() -> io::Result<Self> {
+        let (input, output) = open_tty()?;
+
+        Ok(Question::new(io::BufReader::new(input), output)
+            .ansi(cfg!(unix))
+            .width(terminal_width())
+            .accessible(accessible_from_env())
+            .color_depth(ColorDepth::from_env())
+            .hyperlinks(hyperlinks_from_env()))
+    }
+}
+
+#[cfg(unix)]
+impl Question<io::BufReader<UnixStream>, UnixStream> {
+    /// Asks questions to the next client connecting to `listener`, e.g. for a
+    /// daemon to get answers from a separate process attached to it.
+    pub fn accept_unix(listener: &UnixListener) -> io::Result<Self> {
+        let (stream, _) = listener.accept()?;
+
+        Self::unix(stream)
+    }
+
+    /// Asks questions over `stream`, the answers being read from it as well.
+    pub fn unix(stream: UnixStream) -> io::Result<Self> {
+        Ok(Question::new(
+            io::BufReader::new(stream.try_clone()?),
+            stream,
+        ))
+    }
+}
+
+#[cfg(all(feature = "tty", unix))]
+fn open_tty() -> io::Result<(fs::File, fs::File)> {
+    let tty = fs::File::options()
+        .read(true)
+        .write(true)
+        .open("/dev/tty")?;
+
+    Ok((tty.try_clone()?, tty))
+}
+
+#[cfg(all(feature = "tty", windows))]
+fn open_tty() -> io::Result<(fs::File, fs::File)> {
+    let input = fs::File::options().read(true).write(true).open("CONIN$")?;
+    let output = fs::File::options().write(true).open("CONOUT$")?;
+
+    Ok((input, output))
+}
+
+#[cfg(all(feature = "tty", not(any(unix, windows))))]
+fn open_tty() -> io::Result<(fs::File, fs::File)> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "no controlling terminal on this platform",
+    ))
+}
+
+/// A [`Question`] whose reader and writer are chosen at runtime.
+pub type BoxedQuestion<'a> = Question<Box<dyn io::BufRead + 'a>, Box<dyn io::Write + 'a>>;
+
+impl<'a> BoxedQuestion<'a> {
+    pub fn boxed(reader: Box<dyn io::BufRead + 'a>, writer: Box<dyn io::Write + 'a>) -> Self {
+        Question::new(reader, writer)
     }
 }
 
 impl<R: io::BufRead, W: io::Write> Question<R, W> {
     pub fn new(reader: R, writer: W) -> Self {
-        Self { reader, writer }
+        Self {
+            reader,
+            writer,
+            frame: Vec::new(),
+            theme: Theme::default(),
+            messages: Messages::default(),
+            ansi: false,
+            width: DEFAULT_WIDTH,
+            interactive: true,
+            accessible: false,
+            skip_token: String::from("-"),
+            blank_is_empty: true,
+            matching: Matching::default(),
+            observer: Observer::default(),
+            color_depth: ColorDepth::default(),
+            hyperlinks: false,
+            markdown: false,
+            lockout: None,
+            prompt: Prompt::default(),
+            echo: true,
+            shown: String::new(),
+            echoed: None,
+            transient: false,
+            rows: 0,
+            error_rows: 0,
+            failures: 0,
+            mistyped_phrases: 0,
+            recorder: None,
+        }
     }
 
-    pub fn ask(&mut self, question: impl Into<String>) -> io::Result<Option<String>> {
-        let question = ensure_ends_with_whitespace(question);
+    /// Adapts to the standard input being a terminal and to the output, which
+    /// is a terminal when `terminal` is set.
+    fn detect_terminal(self, terminal: bool) -> Self {
+        let ansi = io::stdin().is_terminal() && terminal && supports_vt();
+
+        self.ansi(ansi)
+            .width(terminal_width())
+            .accessible(accessible_from_env())
+            .color_depth(ColorDepth::from_env())
+            .hyperlinks(hyperlinks_from_env())
+    }
+
+    pub fn theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// Replaces the texts displayed by the questions themselves, e.g. to
+    /// translate them.
+    pub fn messages(mut self, messages: Messages) -> Self {
+        self.messages = messages;
+        self
+    }
+
+    /// Allows ANSI escape sequences to be written to tidy up the terminal, e.g.
+    /// to replace a previous validation error instead of appending a new one.
+    pub fn ansi(mut self, ansi: bool) -> Self {
+        self.ansi = ansi;
+        self
+    }
+
+    /// Sets how many colors the terminal displays, to render the colors of the
+    /// theme as close as it allows.
+    pub fn color_depth(mut self, color_depth: ColorDepth) -> Self {
+        self.color_depth = color_depth;
+        self
+    }
+
+    /// Tells whether the terminal displays OSC 8 hyperlinks, i.e. clickable
+    /// text leading to a URL.
+    pub fn hyperlinks(mut self, hyperlinks: bool) -> Self {
+        self.hyperlinks = hyperlinks;
+        self
+    }
+
+    /// Returns `text` linking to `url`, to be written in a question: clickable
+    /// when the terminal displays hyperlinks, followed by the URL otherwise.
+    pub fn link(&self, text: &str, url: &str) -> String {
+        if self.hyperlinks && self.decorated() {
+            format!("\x1b]8;;{url}\x1b\\{text}\x1b]8;;\x1b\\")
+        } else if text == url {
+            url.to_owned()
+        } else {
+            format!("{text} ({url})")
+        }
+    }
+
+    /// Renders the markdown subset of the questions: `**bold**`, `` `code` ``
+    /// and bullet lists starting with `- ` or `* `, styled on terminals
+    /// accepting ANSI escape sequences.
+    pub fn markdown(mut self, markdown: bool) -> Self {
+        self.markdown = markdown;
+        self
+    }
+
+    /// Rewrites the line of an answered question as `✔ question … answer` on
+    /// terminals accepting ANSI escape sequences, masking secrets, which is
+    /// the default.
+    pub fn echo_answers(mut self, echo: bool) -> Self {
+        self.echo = echo;
+        self
+    }
+
+    /// Erases the lines of an answered question on terminals accepting ANSI
+    /// escape sequences, leaving nothing of it in the scrollback rather than
+    /// echoing the answer.
+    pub fn transient(mut self, transient: bool) -> Self {
+        self.transient = transient;
+        self
+    }
+
+    /// Records the session into `cast` in the asciinema format, e.g. to replay
+    /// a reported issue with `asciinema play`: the output along with its
+    /// timing, and the answers, the secret ones masked; the cast has the width
+    /// set so far.
+    pub fn record(mut self, cast: impl io::Write + Send + 'static) -> Self {
+        self.recorder = Some(Recorder::new(Box::new(cast), self.width));
+        self
+    }
 
-        self.writer.write_all(question.as_bytes())?;
-        self.writer.flush()?;
+    /// Sets the width of the terminal, in columns, used to lay out long lists
+    /// and to clear the lines wrapping around it.
+    pub fn width(mut self, width: usize) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Tells whether someone answers the questions; when not, e.g. because the
+    /// answers are piped in, nothing gets written: neither the questions nor
+    /// the validation errors.
+    pub fn interactive(mut self, interactive: bool) -> Self {
+        self.interactive = interactive;
+        self
+    }
+
+    /// Stays non-interactive (see [`Question::interactive`]) when the standard
+    /// input isn't a terminal, so that `echo answers | mytool` writes nothing
+    /// but its results; the questions are written by default, whatever the
+    /// standard input.
+    pub fn silent_when_piped(self) -> Self {
+        let interactive = io::stdin().is_terminal();
+
+        self.interactive(interactive)
+    }
+
+    /// Suits screen readers: nothing gets redrawn in place, neither styled nor
+    /// cleared, and the choices are listed one per line. The questions asking
+    /// on the standard streams or the terminal turn this on when the
+    /// `ACCESSIBLE` environment variable is set to anything but `0`.
+    pub fn accessible(mut self, accessible: bool) -> Self {
+        self.accessible = accessible;
+        self
+    }
+
+    /// Sets the answer skipping an optional question, `-` by default.
+    pub fn skip_token(mut self, token: impl Into<String>) -> Self {
+        self.skip_token = token.into();
+        self
+    }
+
+    /// Tells whether the answers made of whitespace only count as empty, e.g.
+    /// resolving to a default or being refused as a required answer, which
+    /// they do by default; otherwise, they are kept as typed.
+    pub fn blank_is_empty(mut self, blank_is_empty: bool) -> Self {
+        self.blank_is_empty = blank_is_empty;
+        self
+    }
+
+    /// Tells whether the answers matched against a list, e.g. the choices,
+    /// ignore the case, so that `PROD` stands for `prod`; confirmations
+    /// always ignore it.
+    pub fn ignore_case(mut self, ignore_case: bool) -> Self {
+        self.matching.ignore_case = ignore_case;
+        self
+    }
+
+    /// Tells whether the answers matched against a list, e.g. the choices,
+    /// ignore the accents of the Latin letters, so that `general` stands for
+    /// `général`.
+    pub fn fold_accents(mut self, fold_accents: bool) -> Self {
+        self.matching.fold_accents = fold_accents;
+        self
+    }
+
+    /// Resolves the answers within `distance` edits of a single choice, e.g.
+    /// `stagin` for `staging`, to that choice; the default, 0, resolves none.
+    pub fn typo_tolerance(mut self, distance: usize) -> Self {
+        self.matching.typo_tolerance = distance;
+        self
+    }
+
+    /// Tells whether the typos resolving to a choice must be confirmed first.
+    pub fn confirm_typos(mut self, confirm_typos: bool) -> Self {
+        self.matching.confirm_typos = confirm_typos;
+        self
+    }
+
+    /// Tells `observer` about the questions being asked.
+    pub fn observer(mut self, observer: impl PromptObserver + Send + 'static) -> Self {
+        self.observer = Observer::new(observer);
+        self
+    }
+
+    /// Slows down or aborts the questions refusing their answers too many
+    /// times, e.g. asking for a PIN.
+    pub fn lockout(mut self, lockout: Lockout) -> Self {
+        self.lockout = Some(lockout);
+        self
+    }
+
+    pub fn ask(&mut self, question: impl Into<String>) -> io::Result<Option<String>> {
+        self.write_question(question)?;
 
         let mut buffer = String::new();
         let n = self.reader.read_line(&mut buffer)?;
 
-        Ok(match n {
-            0 => None,
-            _ => Some(buffer.trim().to_owned()),
+        self.answer(n, buffer)
+    }
+
+    /// Asks a question expecting the `prompt` kind of input, led by its icon.
+    pub(crate) fn ask_as(
+        &mut self,
+        prompt: Prompt,
+        question: impl Into<String>,
+    ) -> io::Result<Option<String>> {
+        let previous = std::mem::replace(&mut self.prompt, prompt);
+        let answer = self.ask(question);
+
+        self.prompt = previous;
+        answer
+    }
+
+    /// Asks a question and stores the trimmed answer into `answer`, reusing its
+    /// allocation; returns `false` for EOF.
+    ///
+    /// Once the internal buffers have grown, this doesn't allocate, which
+    /// suits loops asking many questions.
+    pub fn ask_into(&mut self, question: &str, answer: &mut String) -> io::Result<bool> {
+        self.write_question_str(question)?;
+        answer.clear();
+
+        if self.reader.read_line(answer)? == 0 {
+            self.observer.cancelled();
+
+            return Ok(false);
+        }
+
+        let range = self.answer_range(answer);
+
+        answer.truncate(range.end);
+        answer.drain(..range.start);
+        self.observer.answered();
+        self.record_input(answer);
+
+        Ok(true)
+    }
+
+    /// Asks a question until its answer passes `validator`, displaying the
+    /// validation error after each invalid answer.
+    pub fn ask_validated(
+        &mut self,
+        question: impl Into<String>,
+        validator: impl Fn(&str) -> Result<(), String>,
+    ) -> io::Result<Option<String>> {
+        let question = question.into();
+
+        self.validating(|this| loop {
+            let Some(answer) = this.ask(question.as_str())? else {
+                return Ok(None);
+            };
+
+            match validator(&answer) {
+                Ok(()) => return Ok(Some(answer)),
+                Err(message) => this.write_error(&message)?,
+            }
+        })
+    }
+
+    /// Asks a question until the answer isn't empty, displaying the `required`
+    /// message otherwise.
+    pub fn ask_required(&mut self, question: impl Into<String>) -> io::Result<Option<String>> {
+        let message = self.messages.format("required", &[]);
+
+        self.ask_validated(question, |answer| {
+            if answer.is_empty() {
+                Err(message.clone())
+            } else {
+                Ok(())
+            }
         })
     }
+
+    /// Asks a question until its answer parses as a `T`, displaying the parse
+    /// error after each answer which doesn't.
+    pub fn ask_parse_retry<T: FromStr>(
+        &mut self,
+        question: impl Into<String>,
+    ) -> io::Result<Option<T>>
+    where
+        T::Err: fmt::Display,
+    {
+        self.parse(question).ask()
+    }
+
+    /// Asks a question which can be skipped by answering the skip token, as
+    /// the question hints.
+    pub fn ask_optional(&mut self, question: impl Into<String>) -> io::Result<Option<Answer>> {
+        let hint = self
+            .messages
+            .format("skip-hint", &[("token", &self.skip_token)]);
+        let question = format!("{} {hint}", question.into());
+
+        Ok(self.ask(question)?.map(|answer| {
+            if answer == self.skip_token {
+                Answer::Skipped
+            } else {
+                Answer::Given(answer)
+            }
+        }))
+    }
+
+    /// Asks a yes/no question, where an empty answer stands for `default`.
+    pub fn confirm(
+        &mut self,
+        question: impl Into<String>,
+        default: bool,
+    ) -> io::Result<Option<bool>> {
+        let hint = if default {
+            "confirm-hint-yes"
+        } else {
+            "confirm-hint-no"
+        };
+        let question = format!("{} {}", question.into(), self.messages.format(hint, &[]));
+        let yes = self.messages.list("confirm-yes");
+        let no = self.messages.list("confirm-no");
+        let matching = Matching {
+            ignore_case: true,
+            ..self.matching
+        };
+
+        self.validating(|this| loop {
+            let Some(answer) = this.ask_as(Prompt::Confirm, question.as_str())? else {
+                return Ok(None);
+            };
+
+            if answer.is_empty() {
+                return Ok(Some(default));
+            } else if yes.iter().any(|yes| matching.eq(&answer, yes)) {
+                return Ok(Some(true));
+            } else if no.iter().any(|no| matching.eq(&answer, no)) {
+                return Ok(Some(false));
+            }
+
+            let message = this.messages.format("confirm-invalid", &[]);
+
+            this.write_error(&message)?;
+        })
+    }
+
+    /// Asks a question until the answer is a single character among `allowed`,
+    /// e.g. for `[a]dd / [r]emove / [q]uit` menus, and returns it.
+    pub fn ask_char(
+        &mut self,
+        question: impl Into<String>,
+        allowed: &str,
+    ) -> io::Result<Option<char>> {
+        let question = question.into();
+
+        self.validating(|this| loop {
+            let Some(answer) = this.ask(question.as_str())? else {
+                return Ok(None);
+            };
+            let found = allowed
+                .chars()
+                .find(|allowed| this.matching.eq(&answer, allowed.encode_utf8(&mut [0; 4])));
+
+            if let Some(found) = found {
+                return Ok(Some(found));
+            }
+
+            let allowed: Vec<String> = allowed.chars().map(String::from).collect();
+            let message = this
+                .messages
+                .format("char-invalid", &[("allowed", &allowed.join(", "))]);
+
+            this.write_error(&message)?;
+        })
+    }
+
+    /// Displays `code` above the next question, e.g. a confirmation to apply
+    /// it, preserving its indentation; on terminals accepting ANSI escape
+    /// sequences, its long lines are truncated rather than wrapped, and the
+    /// added and removed lines are colored when `language` is `diff`.
+    pub fn show_code(&mut self, code: &str, language: &str) -> io::Result<()> {
+        let decorated = self.decorated();
+
+        if decorated {
+            self.frame.extend_from_slice(b"\x1b[?7l");
+        }
+
+        for line in code.lines() {
+            let color = match line.chars().next() {
+                Some('+') if decorated && language == "diff" => Some("\x1b[32m"),
+                Some('-') if decorated && language == "diff" => Some("\x1b[31m"),
+                _ => None,
+            };
+
+            match color {
+                Some(color) => writeln!(self.frame, "{color}{line}\x1b[39m")?,
+                None => writeln!(self.frame, "{line}")?,
+            }
+        }
+
+        if decorated {
+            self.frame.extend_from_slice(b"\x1b[?7h");
+        }
+
+        Ok(())
+    }
+
+    /// Asks a question and reads all the remaining input as its answer, e.g.
+    /// when the answer is piped in; the answer is trimmed.
+    pub fn ask_all(&mut self, question: impl Into<String>) -> io::Result<String> {
+        self.write_question(question)?;
+
+        let mut buffer = String::new();
+
+        self.reader.read_to_string(&mut buffer)?;
+
+        let answer = buffer.trim().to_owned();
+
+        self.observer.answered();
+        self.record_input(&answer);
+
+        Ok(answer)
+    }
+
+    /// Asks a question and reads one answer per line until EOF or a line equal
+    /// to `terminator`, e.g. an empty line.
+    pub fn ask_lines(
+        &mut self,
+        question: impl Into<String>,
+        terminator: &str,
+    ) -> io::Result<Vec<String>> {
+        self.write_question(question)?;
+
+        let mut answers = Vec::new();
+        let mut buffer = String::new();
+
+        while self.reader.read_line(&mut buffer)? > 0 {
+            let answer = buffer.trim();
+
+            self.record_input(answer);
+
+            if answer == terminator {
+                break;
+            }
+
+            answers.push(answer.to_owned());
+            buffer.clear();
+        }
+
+        self.observer.answered();
+
+        Ok(answers)
+    }
+
+    /// Asks a question and streams everything read until EOF into `sink`,
+    /// without holding the whole answer in memory; returns the number of bytes
+    /// streamed.
+    pub fn ask_to_writer(
+        &mut self,
+        question: impl Into<String>,
+        sink: &mut impl io::Write,
+    ) -> io::Result<u64> {
+        self.write_question(question)?;
+
+        let streamed = io::copy(
+            &mut self.reader,
+            &mut Tee {
+                sink,
+                recorder: self.recorder.as_mut(),
+            },
+        )?;
+
+        self.observer.answered();
+
+        Ok(streamed)
+    }
+
+    /// Asks a question and returns the raw bytes of the answer.
+    ///
+    /// The answer is neither validated as UTF-8 nor trimmed; only the line
+    /// terminator (`\n` or `\r\n`) is removed.
+    pub fn ask_bytes(&mut self, question: impl Into<String>) -> io::Result<Option<Vec<u8>>> {
+        self.write_question(question)?;
+
+        let mut buffer = Vec::new();
+
+        if self.reader.read_until(b'\n', &mut buffer)? == 0 {
+            self.observer.cancelled();
+
+            return Ok(None);
+        }
+
+        let answer = strip_line_terminator(buffer);
+
+        self.observer.answered();
+        self.record_input(&String::from_utf8_lossy(&answer));
+
+        Ok(Some(answer))
+    }
+
+    fn write_question(&mut self, question: impl Into<String>) -> io::Result<()> {
+        self.write_question_str(&question.into())
+    }
+
+    /// Writes `question` followed by a space unless it ends with one, without
+    /// allocating once the internal buffers have grown, e.g. for
+    /// [`Question::ask_into`].
+    fn write_question_str(&mut self, question: &str) -> io::Result<()> {
+        self.observer.shown(question);
+
+        let rendered;
+        let question = match self.markdown {
+            true => {
+                rendered = markdown::render(question, self.decorated());
+                rendered.as_str()
+            }
+            false => question,
+        };
+        let icon = self.theme.icons.get(self.prompt);
+
+        self.echoed = None;
+        self.rows = 0;
+        self.shown.clear();
+        self.shown.push_str(question.trim_end());
+
+        if !icon.is_empty() {
+            write!(self.frame, "{icon} ")?;
+        }
+
+        match self.theme.accent.filter(|_| self.decorated()) {
+            Some(accent) => {
+                let text = question.trim_end();
+                let color = accent.foreground(self.color_depth);
+
+                write!(
+                    self.frame,
+                    "{color}{text}\x1b[0m{}",
+                    &question[text.len()..]
+                )?;
+            }
+            None => self.frame.extend_from_slice(question.as_bytes()),
+        }
+
+        if !question.ends_with(' ') {
+            self.frame.push(b' ');
+        }
+
+        self.render()
+    }
+
+    /// Returns the answer read into `buffer`, or `None` for EOF, i.e. when `n`
+    /// is 0, telling the observer and echoing the answer; `buffer` is wiped,
+    /// since it may hold a secret.
+    fn answer(&mut self, n: usize, buffer: String) -> io::Result<Option<String>> {
+        if n == 0 {
+            self.observer.cancelled();
+
+            return Ok(None);
+        }
+
+        self.observer.answered();
+
+        let answer = buffer[self.answer_range(&buffer)].to_owned();
+
+        secret::wipe(buffer);
+
+        self.record_input(&answer);
+        self.rows = self.rows_above()
+            + self.width_rows(self.last_line_width() + 1 + answer.chars().count());
+
+        if self.transient && self.decorated() {
+            self.clear_lines(self.rows)?;
+            self.rows = 0;
+            self.render()?;
+        } else if self.echo && self.decorated() {
+            let shown = match self.prompt {
+                Prompt::Secret => SECRET_MASK,
+                _ => &answer,
+            };
+
+            self.echoed = Some(shown.to_owned());
+            self.echo("\x1b[32m✔")?;
+            self.render()?;
+        }
+
+        Ok(Some(answer))
+    }
+
+    /// Runs `ask`, a question asking again until its answer is valid, telling
+    /// the observer about the valid answer only.
+    pub(crate) fn validating<T>(
+        &mut self,
+        ask: impl FnOnce(&mut Self) -> io::Result<Option<T>>,
+    ) -> io::Result<Option<T>> {
+        self.observer.validating += 1;
+        self.reset_refusals();
+
+        let answer = ask(self);
+
+        self.observer.validating -= 1;
+        self.reset_refusals();
+
+        if matches!(answer, Ok(Some(_))) {
+            self.observer.answered();
+        }
+
+        answer
+    }
+
+    /// Tells the observer about the answer accepted once asked again, leaving
+    /// its refusals on the screen, e.g. for the questions of a form.
+    pub(crate) fn accepted(&mut self) {
+        self.reset_refusals();
+        self.observer.answered();
+    }
+
+    /// Tells the observer and the recorder about the question of a form asked
+    /// next, i.e. its `id`, `tags` and `metadata`, until [`Question::leave`].
+    pub(crate) fn enter(&mut self, id: &str, tags: &[String], metadata: &[(String, String)]) {
+        self.observer.enter(id, tags, metadata);
+
+        if let Some(recorder) = &mut self.recorder {
+            recorder.marker(id, tags, metadata);
+        }
+    }
+
+    pub(crate) fn leave(&mut self) {
+        self.observer.leave();
+    }
+
+    /// Forgets the refusals of the question asked last, before asking another.
+    pub(crate) fn reset_refusals(&mut self) {
+        self.error_rows = 0;
+        self.failures = 0;
+    }
+
+    /// Records an answer, replaced with the `redacted` message when secret, if
+    /// the session is recorded.
+    pub(crate) fn record_input(&mut self, answer: &str) {
+        if let Some(recorder) = &mut self.recorder {
+            match self.prompt {
+                Prompt::Secret => recorder.input(&self.messages.format("redacted", &[])),
+                _ => recorder.input(answer),
+            }
+        }
+    }
+
+    /// Writes and flushes the pending frame in one go, avoiding flicker.
+    fn render(&mut self) -> io::Result<()> {
+        if !self.interactive {
+            self.frame.clear();
+            return Ok(());
+        }
+
+        let written = self.writer.write_all(&self.frame);
+
+        if let Some(recorder) = &mut self.recorder {
+            recorder.output(&self.frame);
+        }
+
+        self.frame.clear();
+        written?;
+        self.writer.flush()
+    }
+
+    /// Returns the range of the answer in `line`, which is trimmed unless it
+    /// is blank and blank answers don't count as empty; then, only its line
+    /// terminator is left out.
+    fn answer_range(&self, line: &str) -> Range<usize> {
+        let start = line.len() - line.trim_start().len();
+        let end = line.trim_end().len().max(start);
+
+        if start == end && !self.blank_is_empty {
+            let line = line.strip_suffix('\n').unwrap_or(line);
+
+            0..line.strip_suffix('\r').unwrap_or(line).len()
+        } else {
+            start..end
+        }
+    }
+
+    /// Tells whether ANSI escape sequences can style the output or move the
+    /// cursor around.
+    fn decorated(&self) -> bool {
+        self.ansi && !self.accessible
+    }
+
+    /// Moves the cursor up `count` lines and clears everything from there.
+    fn clear_lines(&mut self, count: usize) -> io::Result<()> {
+        match count {
+            0 => Ok(()),
+            count => write!(self.frame, "\x1b[{count}A\r\x1b[J"),
+        }
+    }
+
+    /// Returns the rows taken by `width` columns, wrapped at the width of the
+    /// terminal.
+    fn width_rows(&self, width: usize) -> usize {
+        width.div_ceil(self.width.max(1)).max(1)
+    }
+
+    /// Returns the rows taken by the lines of `text`.
+    fn text_rows(&self, text: &str) -> usize {
+        text.split('\n')
+            .map(|line| self.width_rows(visible_width(line)))
+            .sum()
+    }
+
+    /// Returns the rows taken by the lines of the question last shown above
+    /// its last one, the first being led by the icon of the prompt.
+    fn rows_above(&self) -> usize {
+        let Some((above, _)) = self.shown.rsplit_once('\n') else {
+            return 0;
+        };
+
+        above
+            .split('\n')
+            .enumerate()
+            .map(|(index, line)| {
+                let icon = match index {
+                    0 => self.icon_width(),
+                    _ => 0,
+                };
+
+                self.width_rows(icon + visible_width(line))
+            })
+            .sum()
+    }
+
+    /// Returns the columns taken by the last line of the question last shown,
+    /// led by the icon of the prompt when it is the only one.
+    fn last_line_width(&self) -> usize {
+        match self.shown.rsplit_once('\n') {
+            Some((_, last)) => visible_width(last),
+            None => self.icon_width() + visible_width(&self.shown),
+        }
+    }
+
+    /// Returns the columns taken by the icon of the prompt and its space.
+    fn icon_width(&self) -> usize {
+        match self.theme.icons.get(self.prompt) {
+            "" => 0,
+            icon => visible_width(icon) + 1,
+        }
+    }
+
+    /// Rewrites the last line of the question last shown with the echoed
+    /// answer, led by the colored `mark`.
+    fn echo(&mut self, mark: &str) -> io::Result<()> {
+        let above = self.rows_above();
+
+        self.clear_lines(self.rows.saturating_sub(above))?;
+        self.rows = above + self.write_echo(mark)?;
+
+        Ok(())
+    }
+
+    /// Writes the last line of the question last shown with the echoed
+    /// answer, led by the colored `mark`, and returns the rows it takes.
+    fn write_echo(&mut self, mark: &str) -> io::Result<usize> {
+        let question = self.shown.rsplit('\n').next().unwrap_or_default();
+        let mut width = 2 + visible_width(question);
+
+        write!(self.frame, "{mark}\x1b[39m {question}")?;
+
+        match self.echoed.as_deref() {
+            Some("") | None => writeln!(self.frame)?,
+            Some(answer) => {
+                width += 3 + answer.chars().count();
+                writeln!(self.frame, " … \x1b[36m{answer}\x1b[39m")?;
+            }
+        }
+
+        Ok(self.width_rows(width))
+    }
+
+    /// Writes why the last answer was refused, in the error style of the
+    /// theme, before asking the question again, then applies the lockout
+    /// policy; on terminals accepting ANSI escape sequences, the previous
+    /// attempt and its refusal are replaced, the echo of the answer being
+    /// marked as refused.
+    fn write_error(&mut self, message: &str) -> io::Result<()> {
+        self.observer.failed(message);
+
+        let styled;
+        let message = match self.theme.error {
+            Some(color) if self.decorated() => {
+                let bold = if self.theme.high_contrast {
+                    "\x1b[1m"
+                } else {
+                    ""
+                };
+
+                styled = format!(
+                    "{bold}{}{message}\x1b[0m",
+                    color.foreground(self.color_depth)
+                );
+                styled.as_str()
+            }
+            Some(_) if self.accessible => {
+                styled = format!("{} {message}", self.messages.format("error-label", &[]));
+                styled.as_str()
+            }
+            _ => message,
+        };
+
+        if self.decorated() {
+            self.clear_lines(self.rows + self.error_rows)?;
+            self.rows = 0;
+            self.error_rows = match self.echoed.is_some() {
+                true => self.write_echo("\x1b[31m✘")?,
+                false => 0,
+            };
+            self.echoed = None;
+        }
+
+        writeln!(self.frame, "{message}")?;
+        self.error_rows += self.text_rows(message);
+        self.ring_bell()?;
+        self.failures += 1;
+        self.lock_out(self.failures)
+    }
+
+    /// Rings the bell of the theme on terminals accepting ANSI escape
+    /// sequences, the visual one being audible when accessible.
+    fn ring_bell(&mut self) -> io::Result<()> {
+        if !self.ansi {
+            return Ok(());
+        }
+
+        match self.theme.bell {
+            Bell::Off => {}
+            Bell::Audible => self.frame.push(b'\x07'),
+            Bell::Visual if self.accessible => self.frame.push(b'\x07'),
+            Bell::Visual if self.interactive => {
+                self.frame.extend_from_slice(b"\x1b[?5h");
+                self.render()?;
+                thread::sleep(FLASH);
+                self.frame.extend_from_slice(b"\x1b[?5l");
+            }
+            Bell::Visual => {}
+        }
+
+        Ok(())
+    }
+}
+
+/// Reads whether the `ACCESSIBLE` environment variable asks for questions
+/// suiting screen readers.
+fn accessible_from_env() -> bool {
+    env::var_os("ACCESSIBLE").is_some_and(|value| !value.is_empty() && value != "0")
+}
+
+/// Tells whether the console interprets VT escape sequences, which legacy
+/// Windows consoles don't, unlike Windows Terminal and the terminals setting
+/// `TERM` (e.g. mintty), `TERM_PROGRAM`, `ANSICON` or `ConEmuANSI`.
+fn supports_vt() -> bool {
+    !cfg!(windows)
+        || ["WT_SESSION", "TERM", "TERM_PROGRAM", "ANSICON"]
+            .iter()
+            .any(|name| env::var_os(name).is_some())
+        || env::var("ConEmuANSI").is_ok_and(|value| value == "ON")
+}
+
+/// Tells whether the terminal is known to display OSC 8 hyperlinks.
+fn hyperlinks_from_env() -> bool {
+    let term_program = env::var("TERM_PROGRAM").unwrap_or_default();
+    let vte_version = env::var("VTE_VERSION")
+        .ok()
+        .and_then(|v| v.parse::<u32>().ok());
+
+    ["WT_SESSION", "KONSOLE_VERSION", "KITTY_WINDOW_ID"]
+        .iter()
+        .any(|name| env::var_os(name).is_some())
+        || ["iTerm.app", "WezTerm", "vscode"].contains(&term_program.as_str())
+        || vte_version.is_some_and(|version| version >= 5000)
+}
+
+/// Reads the width of the terminal from the `COLUMNS` environment variable.
+fn terminal_width() -> usize {
+    env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.parse().ok())
+        .unwrap_or(DEFAULT_WIDTH)
+}
+
+/// Returns the columns taken by `text`, leaving out its ANSI escape sequences.
+fn visible_width(text: &str) -> usize {
+    let mut chars = text.chars();
+    let mut width = 0;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\x1b' => match chars.next() {
+                Some('[') => {
+                    chars.find(|c| ('\x40'..='\x7e').contains(c));
+                }
+                Some(']') => {
+                    chars.find(|c| matches!(c, '\x07' | '\\'));
+                }
+                _ => {}
+            },
+            _ => width += 1,
+        }
+    }
+
+    width
 }
 
 fn ensure_ends_with_whitespace(source: impl Into<String>) -> String {
@@ -42,12 +1158,82 @@ fn ensure_ends_with_whitespace(source: impl Into<String>) -> String {
     }
 }
 
+fn strip_line_terminator(mut buffer: Vec<u8>) -> Vec<u8> {
+    if buffer.ends_with(b"\n") {
+        buffer.pop();
+
+        if buffer.ends_with(b"\r") {
+            buffer.pop();
+        }
+    }
+
+    buffer
+}
+
+/// Writes into `sink` what [`Question::ask_to_writer`] streams, recording it
+/// along.
+struct Tee<'a, S> {
+    sink: &'a mut S,
+    recorder: Option<&'a mut Recorder>,
+}
+
+impl<S: io::Write> io::Write for Tee<'_, S> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.sink.write(buf)?;
+
+        if let Some(recorder) = &mut self.recorder {
+            recorder.stream(&buf[..written]);
+        }
+
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.sink.flush()
+    }
+}
+
+/// Fills the placeholders of a validation error message: `{value}` with the
+/// refused answer and `{constraint}` with what is expected instead.
+fn fill_message(message: &str, value: &str, constraint: &str) -> String {
+    message
+        .replace("{constraint}", constraint)
+        .replace("{value}", value)
+}
+
+/// Returns the inclusive minimum and maximum, unless unbounded, of `range`.
+#[cfg(any(feature = "select", feature = "validators"))]
+fn inclusive_bounds(range: &impl std::ops::RangeBounds<usize>) -> (usize, Option<usize>) {
+    use std::ops::Bound;
+
+    let min = match range.start_bound() {
+        Bound::Included(min) => *min,
+        Bound::Excluded(min) => min + 1,
+        Bound::Unbounded => 0,
+    };
+    let max = match range.end_bound() {
+        Bound::Included(max) => Some(*max),
+        Bound::Excluded(max) => Some(max.saturating_sub(1)),
+        Bound::Unbounded => None,
+    };
+
+    (min, max)
+}
+
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
 
     const HOW_ARE_YOU: &str = "How are you?";
 
+    fn require_answer(answer: &str) -> Result<(), String> {
+        if answer.is_empty() {
+            Err(String::from("Please answer."))
+        } else {
+            Ok(())
+        }
+    }
+
     #[test]
     fn ask_should_display_the_question() {
         let mut output = io::BufWriter::new(Vec::new());
@@ -63,6 +1249,93 @@ mod tests {
         assert_eq!(question, HOW_ARE_YOU.to_owned() + " ");
     }
 
+    #[test]
+    fn ask_should_color_the_question_with_the_accent() {
+        let mut output = Vec::new();
+
+        Question::new(io::empty(), &mut output)
+            .ansi(true)
+            .color_depth(ColorDepth::TrueColor)
+            .theme(Theme {
+                accent: Some(Color::rgb(0, 135, 255)),
+                ..Theme::default()
+            })
+            .ask(HOW_ARE_YOU)
+            .expect("ask() should not fail");
+
+        assert_eq!(
+            String::from_utf8(output).expect("from_utf8() should not fail"),
+            format!("\x1b[38;2;0;135;255m{HOW_ARE_YOU}\x1b[0m ")
+        );
+    }
+
+    #[test]
+    fn link_should_fall_back_to_the_url_without_hyperlinks() {
+        let question = Question::new(io::empty(), io::sink()).ansi(true);
+
+        assert_eq!(
+            question.link("the docs", "https://example.com"),
+            "the docs (https://example.com)"
+        );
+        assert_eq!(
+            question
+                .hyperlinks(true)
+                .link("the docs", "https://example.com"),
+            "\x1b]8;;https://example.com\x1b\\the docs\x1b]8;;\x1b\\"
+        );
+    }
+
+    #[test]
+    fn ask_should_render_the_markdown_of_the_question() {
+        let mut output = Vec::new();
+
+        Question::new(io::empty(), &mut output)
+            .ansi(true)
+            .markdown(true)
+            .ask("Run `make`?")
+            .expect("ask() should not fail");
+
+        assert_eq!(output, b"Run \x1b[7mmake\x1b[27m? ");
+    }
+
+    #[test]
+    fn ask_char_should_ask_again_until_an_allowed_character() {
+        let mut output = Vec::new();
+        let result = Question::new("add\nx\nR\n".as_bytes(), &mut output)
+            .ignore_case(true)
+            .ask_char("[a]dd / [r]emove / [q]uit?", "arq")
+            .expect("ask_char() should not fail");
+
+        assert_eq!(result, Some('r'));
+        assert_eq!(
+            String::from_utf8(output)
+                .expect("from_utf8() should not fail")
+                .matches("Please answer one of a, r, q.\n")
+                .count(),
+            2
+        );
+    }
+
+    #[test]
+    fn show_code_should_display_the_code_above_the_next_question() {
+        let mut output = Vec::new();
+        let mut question = Question::new("y\n".as_bytes(), &mut output)
+            .ansi(true)
+            .echo_answers(false);
+
+        question
+            .show_code("  a\n-b\n+c", "diff")
+            .expect("show_code() should not fail");
+        question
+            .confirm("Apply?", false)
+            .expect("confirm() should not fail");
+
+        assert_eq!(
+            String::from_utf8(output).expect("from_utf8() should not fail"),
+            "\x1b[?7l  a\n\x1b[31m-b\x1b[39m\n\x1b[32m+c\x1b[39m\n\x1b[?7hApply? [y/N] "
+        );
+    }
+
     #[test]
     fn ask_should_add_a_whitespace_after_the_question() {
         let mut output = io::BufWriter::new(Vec::new());
@@ -126,4 +1399,577 @@ mod tests {
 
         assert_eq!(result, Some(answer));
     }
+
+    #[test]
+    fn ask_validated_should_ask_again_until_the_answer_is_valid() {
+        let mut output = Vec::new();
+        let result = Question::new("\nfine\n".as_bytes(), &mut output)
+            .ask_validated(HOW_ARE_YOU, require_answer)
+            .expect("ask_validated() should not fail");
+        let output = String::from_utf8(output).expect("from_utf8() should not fail");
+
+        assert_eq!(result, Some(String::from("fine")));
+        assert_eq!(output, "How are you? Please answer.\nHow are you? ");
+    }
+
+    /// Returns what rings `bell` after an invalid answer.
+    fn bell(bell: Bell, ansi: bool) -> String {
+        let mut output = Vec::new();
+
+        Question::new("\nfine\n".as_bytes(), &mut output)
+            .theme(Theme {
+                bell,
+                ..Theme::default()
+            })
+            .ansi(ansi)
+            .echo_answers(false)
+            .ask_validated(HOW_ARE_YOU, require_answer)
+            .expect("ask_validated() should not fail");
+
+        let output = String::from_utf8(output).expect("from_utf8() should not fail");
+        let start = output.find('\n').map_or(0, |end| end + 1);
+        let end = output.rfind(HOW_ARE_YOU).unwrap_or(start).max(start);
+
+        output[start..end].to_owned()
+    }
+
+    #[test]
+    fn ask_validated_should_ring_the_bell_of_the_theme_in_ansi_mode() {
+        assert_eq!(bell(Bell::Off, true), "");
+        assert_eq!(bell(Bell::Audible, true), "\x07");
+        assert_eq!(bell(Bell::Visual, true), "\x1b[?5h\x1b[?5l");
+    }
+
+    #[test]
+    fn ask_validated_should_not_ring_the_bell_without_ansi() {
+        assert_eq!(bell(Bell::Audible, false), "");
+        assert_eq!(bell(Bell::Visual, false), "");
+    }
+
+    #[test]
+    fn ask_validated_should_not_ring_the_bell_when_disabled() {
+        let mut output = Vec::new();
+
+        Question::new("\nfine\n".as_bytes(), &mut output)
+            .theme(Theme {
+                bell: Bell::Off,
+                ..Theme::default()
+            })
+            .ansi(true)
+            .ask_validated(HOW_ARE_YOU, require_answer)
+            .expect("ask_validated() should not fail");
+
+        assert!(!output.contains(&b'\x07'));
+    }
+
+    #[test]
+    fn ask_validated_should_replace_the_previous_attempt_in_ansi_mode() {
+        let mut output = Vec::new();
+
+        Question::new("\n\nfine\n".as_bytes(), &mut output)
+            .ansi(true)
+            .echo_answers(false)
+            .ask_validated(HOW_ARE_YOU, require_answer)
+            .expect("ask_validated() should not fail");
+        let output = String::from_utf8(output).expect("from_utf8() should not fail");
+
+        assert_eq!(
+            output,
+            "How are you? \x1b[1A\r\x1b[JPlease answer.\n\
+             How are you? \x1b[2A\r\x1b[JPlease answer.\n\
+             How are you? "
+        );
+    }
+
+    #[test]
+    fn ask_validated_should_clear_every_row_of_the_previous_attempt() {
+        let mut output = Vec::new();
+
+        Question::new("a rather long answer\nfine\n".as_bytes(), &mut output)
+            .ansi(true)
+            .echo_answers(false)
+            .width(20)
+            .ask_validated("Hello!\nHow are you?", |answer| match answer {
+                "fine" => Ok(()),
+                _ => Err(String::from("Please answer fine.")),
+            })
+            .expect("ask_validated() should not fail");
+        let output = String::from_utf8(output).expect("from_utf8() should not fail");
+
+        assert_eq!(
+            output,
+            "Hello!\nHow are you? \x1b[3A\r\x1b[JPlease answer fine.\nHello!\nHow are you? "
+        );
+    }
+
+    #[test]
+    fn confirm_should_replace_the_previous_attempt_in_ansi_mode() {
+        let mut output = Vec::new();
+
+        Question::new("maybe\nmaybe\ny\n".as_bytes(), &mut output)
+            .ansi(true)
+            .echo_answers(false)
+            .confirm("Sure?", false)
+            .expect("confirm() should not fail");
+        let output = String::from_utf8(output).expect("from_utf8() should not fail");
+
+        assert_eq!(
+            output,
+            "Sure? [y/N] \x1b[1A\r\x1b[JPlease answer yes or no.\n\
+             Sure? [y/N] \x1b[2A\r\x1b[JPlease answer yes or no.\n\
+             Sure? [y/N] "
+        );
+    }
+
+    #[test]
+    fn write_error_should_style_the_refusal_with_the_theme() {
+        let written = |question: Question<&[u8], &mut Vec<u8>>| {
+            question
+                .echo_answers(false)
+                .confirm("Sure?", false)
+                .expect("confirm() should not fail");
+        };
+        let theme = Theme {
+            error: Some(Color::rgb(255, 0, 0)),
+            ..Theme::default()
+        };
+        let (mut colored, mut bold, mut labeled) = (Vec::new(), Vec::new(), Vec::new());
+
+        written(
+            Question::new("maybe\ny\n".as_bytes(), &mut colored)
+                .ansi(true)
+                .theme(theme.clone()),
+        );
+        written(
+            Question::new("maybe\ny\n".as_bytes(), &mut bold)
+                .ansi(true)
+                .theme(Theme::high_contrast()),
+        );
+        written(
+            Question::new("maybe\ny\n".as_bytes(), &mut labeled)
+                .ansi(true)
+                .accessible(true)
+                .theme(theme),
+        );
+
+        assert_eq!(
+            colored,
+            "Sure? [y/N] \x1b[1A\r\x1b[J\x1b[91mPlease answer yes or no.\x1b[0m\n\
+             Sure? [y/N] "
+                .as_bytes()
+        );
+        assert_eq!(
+            bold,
+            "Sure? [y/N] \x1b[1A\r\x1b[J\x1b[1m\x1b[91mPlease answer yes or no.\x1b[0m\n\
+             Sure? [y/N] "
+                .as_bytes()
+        );
+        assert_eq!(
+            labeled,
+            "Sure? [y/N] Error: Please answer yes or no.\nSure? [y/N] ".as_bytes()
+        );
+    }
+
+    #[test]
+    fn ask_char_should_replace_the_previous_attempt_in_ansi_mode() {
+        let mut output = Vec::new();
+
+        Question::new("x\nx\na\n".as_bytes(), &mut output)
+            .ansi(true)
+            .echo_answers(false)
+            .ask_char("Action?", "aq")
+            .expect("ask_char() should not fail");
+        let output = String::from_utf8(output).expect("from_utf8() should not fail");
+
+        assert_eq!(
+            output,
+            "Action? \x1b[1A\r\x1b[JPlease answer one of a, q.\n\
+             Action? \x1b[2A\r\x1b[JPlease answer one of a, q.\n\
+             Action? "
+        );
+    }
+
+    #[test]
+    fn confirm_should_display_the_default() {
+        let mut output = Vec::new();
+
+        Question::new(io::empty(), &mut output)
+            .confirm(HOW_ARE_YOU, false)
+            .expect("confirm() should not fail");
+
+        assert_eq!(output, b"How are you? [y/N] ");
+    }
+
+    #[test]
+    fn confirm_should_be_led_by_the_icon_of_its_kind() {
+        let mut output = Vec::new();
+        let mut question = Question::new("fine\n\n".as_bytes(), &mut output).theme(Theme {
+            icons: Icons::symbols(),
+            ..Theme::default()
+        });
+
+        question.ask(HOW_ARE_YOU).expect("ask() should not fail");
+        question
+            .confirm(HOW_ARE_YOU, false)
+            .expect("confirm() should not fail");
+
+        assert_eq!(
+            String::from_utf8(output).expect("from_utf8() should not fail"),
+            "? How are you? ✓ How are you? [y/N] "
+        );
+    }
+
+    #[test]
+    fn ask_should_echo_the_answer_in_ansi_mode() {
+        let mut output = Vec::new();
+        let result = Question::new("fine\n".as_bytes(), &mut output)
+            .ansi(true)
+            .ask(HOW_ARE_YOU)
+            .expect("ask() should not fail");
+
+        assert_eq!(result, Some(String::from("fine")));
+        assert_eq!(
+            String::from_utf8(output).expect("from_utf8() should not fail"),
+            "How are you? \x1b[1A\r\x1b[J\x1b[32m✔\x1b[39m How are you? … \x1b[36mfine\x1b[39m\n"
+        );
+    }
+
+    #[test]
+    fn ask_should_erase_the_question_once_answered_when_transient() {
+        let mut output = Vec::new();
+        let result = Question::new("fine\n".as_bytes(), &mut output)
+            .ansi(true)
+            .markdown(true)
+            .transient(true)
+            .ask("Hello!\nHow are you?")
+            .expect("ask() should not fail");
+
+        assert_eq!(result, Some(String::from("fine")));
+        assert_eq!(output, b"Hello!\nHow are you? \x1b[2A\r\x1b[J");
+    }
+
+    #[test]
+    fn ask_validated_should_not_clear_an_erased_attempt_again() {
+        let mut output = Vec::new();
+
+        Question::new("\n\nfine\n".as_bytes(), &mut output)
+            .ansi(true)
+            .transient(true)
+            .ask_validated(HOW_ARE_YOU, require_answer)
+            .expect("ask_validated() should not fail");
+        let output = String::from_utf8(output).expect("from_utf8() should not fail");
+
+        assert_eq!(
+            output,
+            "How are you? \x1b[1A\r\x1b[JPlease answer.\nHow are you? \x1b[1A\r\x1b[J\
+             \x1b[1A\r\x1b[JPlease answer.\nHow are you? \x1b[1A\r\x1b[J"
+        );
+    }
+
+    #[test]
+    fn confirm_should_mark_the_echo_of_an_invalid_answer() {
+        let mut output = Vec::new();
+
+        Question::new("maybe\ny\n".as_bytes(), &mut output)
+            .ansi(true)
+            .confirm("Sure?", false)
+            .expect("confirm() should not fail");
+        let output = String::from_utf8(output).expect("from_utf8() should not fail");
+
+        assert!(output.contains("\x1b[31m✘\x1b[39m Sure? [y/N] … \x1b[36mmaybe\x1b[39m\n"));
+        assert!(output.ends_with("\x1b[32m✔\x1b[39m Sure? [y/N] … \x1b[36my\x1b[39m\n"));
+    }
+
+    #[test]
+    fn confirm_should_return_the_default_for_an_empty_answer() {
+        let result = Question::new("\n".as_bytes(), io::sink())
+            .confirm(HOW_ARE_YOU, true)
+            .expect("confirm() should not fail");
+
+        assert_eq!(result, Some(true));
+    }
+
+    #[test]
+    fn confirm_should_ask_again_until_the_answer_is_yes_or_no() {
+        let result = Question::new("maybe\nNo\n".as_bytes(), io::sink())
+            .confirm(HOW_ARE_YOU, true)
+            .expect("confirm() should not fail");
+
+        assert_eq!(result, Some(false));
+    }
+
+    #[test]
+    fn confirm_should_use_the_translated_messages() {
+        let mut output = Vec::new();
+        let messages = Messages::english()
+            .set("confirm-hint-yes", "[O/n]")
+            .set("confirm-yes", "o, oui")
+            .set("confirm-invalid", "Veuillez répondre oui ou non.");
+        let result = Question::new("yes\noui\n".as_bytes(), &mut output)
+            .messages(messages)
+            .confirm("Continuer ?", true)
+            .expect("confirm() should not fail");
+        let output = String::from_utf8(output).expect("from_utf8() should not fail");
+
+        assert_eq!(result, Some(true));
+        assert_eq!(
+            output,
+            "Continuer ? [O/n] Veuillez répondre oui ou non.\nContinuer ? [O/n] "
+        );
+    }
+
+    #[test]
+    fn ask_required_should_ask_again_until_the_answer_is_not_empty() {
+        let mut output = Vec::new();
+        let result = Question::new("\n  \nfine\n".as_bytes(), &mut output)
+            .messages(Messages::english().set("required", "Please answer."))
+            .ask_required(HOW_ARE_YOU)
+            .expect("ask_required() should not fail");
+        let output = String::from_utf8(output).expect("from_utf8() should not fail");
+
+        assert_eq!(result, Some(String::from("fine")));
+        assert_eq!(
+            output,
+            "How are you? Please answer.\nHow are you? Please answer.\nHow are you? "
+        );
+    }
+
+    #[test]
+    fn ask_parse_retry_should_display_the_parse_error_and_ask_again() {
+        let mut output = Vec::new();
+        let result = Question::new(
+            "80a
+8080
+"
+            .as_bytes(),
+            &mut output,
+        )
+        .ask_parse_retry::<u16>("Port?")
+        .expect("ask_parse_retry() should not fail");
+
+        assert_eq!(result, Some(8080));
+        assert_eq!(output, b"Port? invalid digit found in string\nPort? ");
+    }
+
+    #[test]
+    fn ask_should_keep_a_blank_answer_unless_blank_is_empty() {
+        let mut question = Question::new("  \n  \r\n".as_bytes(), io::sink());
+
+        assert_eq!(question.ask(HOW_ARE_YOU).ok(), Some(Some(String::new())));
+
+        let mut question = question.blank_is_empty(false);
+
+        assert_eq!(
+            question.ask(HOW_ARE_YOU).ok(),
+            Some(Some(String::from("  ")))
+        );
+    }
+
+    #[test]
+    fn ask_required_should_accept_a_blank_answer_unless_blank_is_empty() {
+        let result = Question::new("  \n".as_bytes(), io::sink())
+            .blank_is_empty(false)
+            .ask_required(HOW_ARE_YOU)
+            .expect("ask_required() should not fail");
+
+        assert_eq!(result, Some(String::from("  ")));
+    }
+
+    #[test]
+    fn ask_optional_should_return_the_given_answer() {
+        let mut output = Vec::new();
+        let result = Question::new("fine\n".as_bytes(), &mut output)
+            .ask_optional(HOW_ARE_YOU)
+            .expect("ask_optional() should not fail");
+
+        assert_eq!(result, Some(Answer::Given(String::from("fine"))));
+        assert_eq!(output, b"How are you? (- to skip) ");
+    }
+
+    #[test]
+    fn ask_optional_should_recognize_the_skip_token() {
+        let result = Question::new("skip\n".as_bytes(), io::sink())
+            .skip_token("skip")
+            .ask_optional(HOW_ARE_YOU)
+            .expect("ask_optional() should not fail");
+
+        assert_eq!(result, Some(Answer::Skipped));
+    }
+
+    #[test]
+    fn stdio_should_have_a_static_lifetime() {
+        fn assert_static<T: 'static>(_: T) {}
+
+        assert_static(Question::stdio());
+    }
+
+    #[test]
+    fn boxed_should_ask_through_the_boxed_streams() {
+        let mut output = Vec::new();
+        let reader: Box<dyn io::BufRead> = Box::new("fine\n".as_bytes());
+        let result = Question::boxed(reader, Box::new(&mut output))
+            .ask(HOW_ARE_YOU)
+            .expect("ask() should not fail");
+
+        assert_eq!(result, Some(String::from("fine")));
+        assert_eq!(output, b"How are you? ");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn accept_unix_should_ask_the_connecting_client() {
+        use std::{fs, io::Read};
+
+        let path = env::temp_dir().join(format!("question-{}.sock", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let listener = UnixListener::bind(&path).expect("bind() should not fail");
+        let client = std::thread::spawn({
+            let path = path.clone();
+
+            move || {
+                let mut stream = UnixStream::connect(path).expect("connect() should not fail");
+                let mut question = [0; 13];
+
+                stream
+                    .read_exact(&mut question)
+                    .expect("read_exact() should not fail");
+                stream
+                    .write_all(b"fine\n")
+                    .expect("write_all() should not fail");
+
+                question
+            }
+        });
+        let mut question = Question::accept_unix(&listener).expect("accept_unix() should not fail");
+        let result = question.ask(HOW_ARE_YOU).expect("ask() should not fail");
+
+        assert_eq!(result, Some(String::from("fine")));
+        assert_eq!(
+            &client.join().expect("join() should not fail"),
+            b"How are you? "
+        );
+        fs::remove_file(path).expect("remove_file() should not fail");
+    }
+
+    /// A writer counting how many times it is written to.
+    #[derive(Default)]
+    pub(crate) struct CountingWriter {
+        pub(crate) writes: usize,
+    }
+
+    impl io::Write for CountingWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.writes += 1;
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn ask_validated_should_write_the_error_and_the_question_at_once() {
+        let mut output = CountingWriter::default();
+
+        Question::new("\nfine\n".as_bytes(), &mut output)
+            .ansi(true)
+            .echo_answers(false)
+            .ask_validated(HOW_ARE_YOU, require_answer)
+            .expect("ask_validated() should not fail");
+
+        assert_eq!(output.writes, 2);
+    }
+
+    #[test]
+    fn ask_into_should_store_the_trimmed_answer_into_the_buffer() {
+        let mut output = Vec::new();
+        let mut answer = String::from("previous answer");
+        let answered = Question::new("  fine \n".as_bytes(), &mut output)
+            .ask_into(HOW_ARE_YOU, &mut answer)
+            .expect("ask_into() should not fail");
+
+        assert!(answered);
+        assert_eq!(answer, "fine");
+        assert_eq!(output, b"How are you? ");
+    }
+
+    #[test]
+    fn ask_into_should_return_false_for_eof() {
+        let mut answer = String::new();
+        let answered = Question::new(io::empty(), io::sink())
+            .ask_into(HOW_ARE_YOU, &mut answer)
+            .expect("ask_into() should not fail");
+
+        assert!(!answered);
+    }
+
+    #[test]
+    fn ask_should_not_write_the_question_when_non_interactive() {
+        let mut output = Vec::new();
+
+        Question::new(io::empty(), &mut output)
+            .interactive(false)
+            .ask(HOW_ARE_YOU)
+            .expect("ask() should not fail");
+
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    fn ask_all_should_read_all_the_remaining_input() {
+        let answer = Question::new("first\nsecond\n".as_bytes(), io::sink())
+            .ask_all("Message?")
+            .expect("ask_all() should not fail");
+
+        assert_eq!(answer, "first\nsecond");
+    }
+
+    #[test]
+    fn ask_lines_should_read_the_answers_until_the_terminator() {
+        let hosts = Question::new("alpha\n beta \n\ngamma\n".as_bytes(), io::sink())
+            .ask_lines("Hosts, one per line:", "")
+            .expect("ask_lines() should not fail");
+
+        assert_eq!(hosts, ["alpha", "beta"]);
+    }
+
+    #[test]
+    fn ask_lines_should_read_the_answers_until_eof() {
+        let hosts = Question::new("alpha\nbeta".as_bytes(), io::sink())
+            .ask_lines("Hosts, one per line:", "")
+            .expect("ask_lines() should not fail");
+
+        assert_eq!(hosts, ["alpha", "beta"]);
+    }
+
+    #[test]
+    fn ask_to_writer_should_stream_the_answer_until_eof() {
+        let mut sink = Vec::new();
+        let n = Question::new("first line\nsecond line\n".as_bytes(), io::sink())
+            .ask_to_writer("Paste the certificate:", &mut sink)
+            .expect("ask_to_writer() should not fail");
+
+        assert_eq!(n, 23);
+        assert_eq!(sink, b"first line\nsecond line\n");
+    }
+
+    #[test]
+    fn ask_bytes_should_return_none_for_eof() {
+        let result = Question::new(io::empty(), io::sink())
+            .ask_bytes(HOW_ARE_YOU)
+            .expect("ask_bytes() should not fail");
+
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn ask_bytes_should_return_the_exact_bytes_without_the_line_terminator() {
+        let answer: &[u8] = b"  fine \xff\r\n";
+        let result = Question::new(answer, io::sink())
+            .ask_bytes(HOW_ARE_YOU)
+            .expect("ask_bytes() should not fail");
+
+        assert_eq!(result, Some(b"  fine \xff".to_vec()));
+    }
 }
@@ -0,0 +1,181 @@
+/// Declares a struct holding the typed answers to a sequence of questions,
+/// along with a `form` function returning the [`Form`](crate::Form) asking
+/// them, e.g. to add a review, and an `ask` function asking it.
+///
+/// Each field is declared with its kind, `text` for a [`String`] or a type
+/// implementing [`FromStr`](std::str::FromStr) such as `u16`, then its
+/// question, an optional default answer and an optional message refusing the
+/// answers which don't parse, as [`Form::parsed_with_message`] takes it;
+/// those answers get asked again. The id of each question is the name of its
+/// field:
+///
+/// ```no_run
+/// use question::{questions, Question};
+///
+/// questions! {
+///     pub struct Setup {
+///         pub name: text("Your name?"),
+///         pub port: u16("Port?", default = 8080, message = "'{value}' is not a port."),
+///     }
+/// }
+///
+/// let setup = Setup::ask(&mut Question::default())?;
+/// let reviewed = Setup::form().review(true).ask(&mut Question::default())?;
+/// let reviewed = Setup::from_answers(&reviewed);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// [`Form::parsed_with_message`]: crate::Form::parsed_with_message
+#[macro_export]
+macro_rules! questions {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            $(
+                $field_vis:vis $field:ident: $kind:ident(
+                    $question:expr
+                    $(, default = $default:expr)?
+                    $(, message = $message:expr)?
+                )
+            ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis struct $name {
+            $($field_vis $field: $crate::questions!(@type $kind),)*
+        }
+
+        impl $name {
+            /// Returns the form asking the questions in order.
+            pub fn form() -> $crate::Form {
+                let form = $crate::Form::new();
+
+                $(
+                    let form = $crate::questions!(
+                        @field form, $kind, ::std::stringify!($field), $question $(, $default)?
+                    );
+                    let form = $crate::questions!(@parse form, $kind $(, $message)?);
+                )*
+
+                form
+            }
+
+            /// Returns the answers of the form as typed, or `None` if one of
+            /// them is missing.
+            pub fn from_answers(answers: &$crate::Answers) -> ::std::option::Option<Self> {
+                ::std::option::Option::Some(Self {
+                    $($field: answers.get(::std::stringify!($field))?.parse().ok()?,)*
+                })
+            }
+
+            /// Asks the questions of the form; returns `None` for EOF.
+            pub fn ask<R: ::std::io::BufRead, W: ::std::io::Write>(
+                question: &mut $crate::Question<R, W>,
+            ) -> ::std::io::Result<::std::option::Option<Self>> {
+                match Self::form().ask(question) {
+                    ::std::result::Result::Ok(answers) => {
+                        ::std::result::Result::Ok(Self::from_answers(&answers))
+                    }
+                    ::std::result::Result::Err($crate::QuestionError::Io(error))
+                        if error.kind() == ::std::io::ErrorKind::UnexpectedEof =>
+                    {
+                        ::std::result::Result::Ok(::std::option::Option::None)
+                    }
+                    ::std::result::Result::Err($crate::QuestionError::Io(error)) => {
+                        ::std::result::Result::Err(error)
+                    }
+                    ::std::result::Result::Err(error) => {
+                        ::std::result::Result::Err(::std::io::Error::other(error))
+                    }
+                }
+            }
+        }
+    };
+    (@type text) => { ::std::string::String };
+    (@type $kind:ident) => { $kind };
+    (@field $form:expr, $kind:ident, $id:expr, $question:expr) => {
+        $form.field($id, $question)
+    };
+    (@field $form:expr, text, $id:expr, $question:expr, $default:expr) => {
+        $form.field_with_default($id, $question, $default)
+    };
+    (@field $form:expr, $kind:ident, $id:expr, $question:expr, $default:expr) => {{
+        let default: $kind = $default;
+
+        $form.field_with_default($id, $question, ::std::string::ToString::to_string(&default))
+    }};
+    (@parse $form:expr, $kind:ident) => {
+        $form.parsed::<$crate::questions!(@type $kind)>()
+    };
+    (@parse $form:expr, $kind:ident, $message:expr) => {
+        $form.parsed_with_message::<$crate::questions!(@type $kind)>($message)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Question;
+    use std::io;
+
+    crate::questions! {
+        #[derive(Debug, PartialEq)]
+        struct Setup {
+            name: text("Your name?"),
+            port: u16("Port?", default = 8080),
+        }
+    }
+
+    crate::questions! {
+        struct Server {
+            port: u16("Port?", message = "'{value}' is not a port."),
+        }
+    }
+
+    #[test]
+    fn questions_should_ask_the_questions_in_order() {
+        let mut output = Vec::new();
+        let setup = Setup::ask(&mut Question::new("Alice\n\n".as_bytes(), &mut output))
+            .expect("ask() should not fail");
+
+        assert_eq!(
+            setup,
+            Some(Setup {
+                name: String::from("Alice"),
+                port: 8080
+            })
+        );
+        assert_eq!(output, b"Your name? Port? (default: 8080) ");
+    }
+
+    #[test]
+    fn questions_should_ask_again_when_an_answer_does_not_parse() {
+        let mut output = Vec::new();
+        let setup = Setup::ask(&mut Question::new(
+            "Alice\nhttp\n4242\n".as_bytes(),
+            &mut output,
+        ))
+        .expect("ask() should not fail");
+        let output = String::from_utf8(output).expect("from_utf8() should not fail");
+
+        assert_eq!(setup.map(|setup| setup.port), Some(4242));
+        assert!(output.contains("'http' is not a valid answer.\n"));
+    }
+
+    #[test]
+    fn questions_should_refuse_an_answer_with_the_custom_message() {
+        let mut output = Vec::new();
+        let server = Server::ask(&mut Question::new("http\n80\n".as_bytes(), &mut output))
+            .expect("ask() should not fail");
+
+        assert_eq!(server.map(|server| server.port), Some(80));
+        assert_eq!(output, b"Port? 'http' is not a port.\nPort? ");
+    }
+
+    #[test]
+    fn questions_should_return_none_for_eof() {
+        let setup = Setup::ask(&mut Question::new("Alice\n".as_bytes(), io::sink()))
+            .expect("ask() should not fail");
+
+        assert!(setup.is_none());
+    }
+}
@@ -0,0 +1,86 @@
+//! The markdown subset of the questions: `**bold**`, `` `code` `` and bullet
+//! lists, whose items start with `- ` or `* `.
+
+/// Renders `text`, styled with ANSI escape sequences when `ansi` is set or
+/// without its markers otherwise.
+pub(crate) fn render(text: &str, ansi: bool) -> String {
+    let mut rendered = String::with_capacity(text.len());
+
+    for (index, line) in text.split('\n').enumerate() {
+        if index > 0 {
+            rendered.push('\n');
+        }
+
+        let indent = line.len() - line.trim_start().len();
+        let line = match line[indent..].strip_prefix(['-', '*']) {
+            Some(item) if item.starts_with(' ') => {
+                rendered.push_str(&line[..indent]);
+                rendered.push_str(if ansi { "•" } else { "-" });
+                item
+            }
+            _ => line,
+        };
+
+        render_spans(&mut rendered, line, ansi);
+    }
+
+    rendered
+}
+
+/// Renders the bold and code spans of `line` into `rendered`; an unclosed
+/// marker is kept as is.
+fn render_spans(rendered: &mut String, mut line: &str, ansi: bool) {
+    while let Some(start) = line.find(['*', '`']) {
+        let (marker, on, off) = match &line[start..] {
+            rest if rest.starts_with("**") => ("**", "\x1b[1m", "\x1b[22m"),
+            rest if rest.starts_with('`') => ("`", "\x1b[7m", "\x1b[27m"),
+            _ => {
+                rendered.push_str(&line[..=start]);
+                line = &line[start + 1..];
+                continue;
+            }
+        };
+        let inner = &line[start + marker.len()..];
+
+        let Some(end) = inner.find(marker) else {
+            rendered.push_str(&line[..start + marker.len()]);
+            line = inner;
+            continue;
+        };
+
+        rendered.push_str(&line[..start]);
+
+        match (ansi, marker) {
+            (true, _) => rendered.extend([on, &inner[..end], off]),
+            (false, "`") => rendered.extend(["`", &inner[..end], "`"]),
+            (false, _) => rendered.push_str(&inner[..end]),
+        }
+
+        line = &inner[end + marker.len()..];
+    }
+
+    rendered.push_str(line);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "Deploy **now**?\n- runs `make`\n  * twice ** maybe";
+
+    #[test]
+    fn render_should_style_the_markdown_with_ansi() {
+        assert_eq!(
+            render(TEXT, true),
+            "Deploy \x1b[1mnow\x1b[22m?\n• runs \x1b[7mmake\x1b[27m\n  • twice ** maybe"
+        );
+    }
+
+    #[test]
+    fn render_should_strip_the_markers_without_ansi() {
+        assert_eq!(
+            render(TEXT, false),
+            "Deploy now?\n- runs `make`\n  - twice ** maybe"
+        );
+    }
+}
@@ -0,0 +1,102 @@
+/// How typed answers are compared to a fixed list of answers, e.g. choices.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct Matching {
+    pub(crate) ignore_case: bool,
+    pub(crate) fold_accents: bool,
+    /// The number of edits within which a typo resolves to a choice.
+    pub(crate) typo_tolerance: usize,
+    pub(crate) confirm_typos: bool,
+}
+
+impl Matching {
+    /// Tells whether `answer` stands for `text`.
+    pub(crate) fn eq(self, answer: &str, text: &str) -> bool {
+        answer == text || self.comparable(answer) == self.comparable(text)
+    }
+
+    /// Returns `text` as compared, i.e. lowercased when the case is ignored
+    /// and without accents when they are folded.
+    pub(crate) fn comparable(self, text: &str) -> String {
+        let text = if self.ignore_case {
+            text.to_lowercase()
+        } else {
+            text.to_owned()
+        };
+
+        if self.fold_accents {
+            text.chars().map(fold_accent).collect()
+        } else {
+            text
+        }
+    }
+}
+
+/// Returns the unaccented letter of the Latin-1 and Latin Extended-A letter
+/// `c`, or `c` itself.
+fn fold_accent(c: char) -> char {
+    match c {
+        'À'..='Å' | 'Ā' | 'Ă' | 'Ą' => 'A',
+        'à'..='å' | 'ā' | 'ă' | 'ą' => 'a',
+        'Ç' | 'Ć' | 'Ĉ' | 'Ċ' | 'Č' => 'C',
+        'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => 'c',
+        'Ď' | 'Đ' => 'D',
+        'ď' | 'đ' => 'd',
+        'È'..='Ë' | 'Ē' | 'Ĕ' | 'Ė' | 'Ę' | 'Ě' => 'E',
+        'è'..='ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => 'e',
+        'Ĝ' | 'Ğ' | 'Ġ' | 'Ģ' => 'G',
+        'ĝ' | 'ğ' | 'ġ' | 'ģ' => 'g',
+        'Ĥ' | 'Ħ' => 'H',
+        'ĥ' | 'ħ' => 'h',
+        'Ì'..='Ï' | 'Ĩ' | 'Ī' | 'Ĭ' | 'Į' | 'İ' => 'I',
+        'ì'..='ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => 'i',
+        'Ĵ' => 'J',
+        'ĵ' => 'j',
+        'Ķ' => 'K',
+        'ķ' => 'k',
+        'Ĺ' | 'Ļ' | 'Ľ' | 'Ŀ' | 'Ł' => 'L',
+        'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => 'l',
+        'Ñ' | 'Ń' | 'Ņ' | 'Ň' => 'N',
+        'ñ' | 'ń' | 'ņ' | 'ň' => 'n',
+        'Ò'..='Ö' | 'Ø' | 'Ō' | 'Ŏ' | 'Ő' => 'O',
+        'ò'..='ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => 'o',
+        'Ŕ' | 'Ŗ' | 'Ř' => 'R',
+        'ŕ' | 'ŗ' | 'ř' => 'r',
+        'Ś' | 'Ŝ' | 'Ş' | 'Š' => 'S',
+        'ś' | 'ŝ' | 'ş' | 'š' => 's',
+        'Ţ' | 'Ť' | 'Ŧ' => 'T',
+        'ţ' | 'ť' | 'ŧ' => 't',
+        'Ù'..='Ü' | 'Ũ' | 'Ū' | 'Ŭ' | 'Ů' | 'Ű' | 'Ų' => 'U',
+        'ù'..='ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => 'u',
+        'Ŵ' => 'W',
+        'ŵ' => 'w',
+        'Ý' | 'Ŷ' | 'Ÿ' => 'Y',
+        'ý' | 'ÿ' | 'ŷ' => 'y',
+        'Ź' | 'Ż' | 'Ž' => 'Z',
+        'ź' | 'ż' | 'ž' => 'z',
+        c => c,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn eq_should_compare_exactly_by_default() {
+        assert!(Matching::default().eq("prod", "prod"));
+        assert!(!Matching::default().eq("PROD", "prod"));
+    }
+
+    #[test]
+    fn eq_should_ignore_the_case_and_fold_the_accents() {
+        let matching = Matching {
+            ignore_case: true,
+            fold_accents: true,
+            ..Matching::default()
+        };
+
+        assert!(matching.eq("PROD", "prod"));
+        assert!(matching.eq("Recette", "récette"));
+        assert!(matching.eq("GÉNÉRAL", "general"));
+    }
+}
@@ -0,0 +1,247 @@
+use std::{collections::HashMap, fmt};
+
+/// The texts the questions display by themselves, e.g. the hints and the
+/// error messages, by id, which can be replaced to translate the questions.
+///
+/// A text refers to its arguments by name in [Fluent] placeables, e.g.
+/// `{ $count }` in `Choose 1-{ $count }:`, the id of the menu prompt.
+///
+/// [Fluent]: https://projectfluent.org
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Messages {
+    texts: HashMap<String, String>,
+}
+
+/// The id and English text of every message.
+const ENGLISH: [(&str, &str); 54] = [
+    ("confirm-hint-yes", "[Y/n]"),
+    ("confirm-hint-no", "[y/N]"),
+    ("confirm-yes", "y, yes"),
+    ("confirm-no", "n, no"),
+    ("confirm-invalid", "Please answer yes or no."),
+    ("command-hint-editable", "[y/N/e]"),
+    ("command-edit-keywords", "e, edit"),
+    ("command-edit", "Command to run instead of `{ $command }`:"),
+    (
+        "command-invalid",
+        "Please answer yes, no or e to edit the command.",
+    ),
+    ("overwrite-prompt", "Overwrite { $path }? [o/s/f/a]"),
+    ("overwrite-keywords", "o, s, f, a"),
+    (
+        "overwrite-invalid",
+        "Please answer o to overwrite, s to skip, f to show the full file or a to abort.",
+    ),
+    ("default-hint", "(default: { $default })"),
+    ("skip-hint", "({ $token } to skip)"),
+    ("required", "This field is required."),
+    ("mismatch", "The answers don't match."),
+    ("review-prompt", "Does this look right?"),
+    ("review-hint", "[Y/n/1-{ $count }]"),
+    (
+        "review-invalid",
+        "Please answer yes, no or a number between 1 and { $count }.",
+    ),
+    ("invalid-answer", "'{ $value }' is not a valid answer."),
+    (
+        "choice-unavailable",
+        "'{ $label }' is unavailable: { $reason }.",
+    ),
+    ("choice-suggestion", "Did you mean '{ $label }'?"),
+    ("choice-invalid", "Please answer one of { $labels }."),
+    ("select-prompt", "Choose 1-{ $count }:"),
+    (
+        "select-invalid",
+        "Please choose a number between 1 and { $count }.",
+    ),
+    (
+        "multi-select-prompt",
+        "Choose among 1-{ $count }, separated by commas (or { $all }, { $none }, { $invert }):",
+    ),
+    ("multi-select-keywords", "all, none, invert"),
+    (
+        "multi-select-unknown",
+        "'{ $token }' is not one of the choices.",
+    ),
+    ("count-exactly", "Please choose exactly { $min }."),
+    (
+        "count-between",
+        "Please choose between { $min } and { $max }.",
+    ),
+    ("count-at-most", "Please choose at most { $max }."),
+    ("count-at-least", "Please choose at least { $min }."),
+    (
+        "color-invalid",
+        "Please answer a color such as #ff8700 or rgb(255, 135, 0).",
+    ),
+    ("color-confirm", "Use this color?"),
+    ("char-invalid", "Please answer one of { $allowed }."),
+    (
+        "stepper-hint",
+        "[{ $value }] (+/- by { $step }, { $min }-{ $max })",
+    ),
+    (
+        "stepper-invalid",
+        "Please answer +, - or a number between { $min } and { $max }.",
+    ),
+    (
+        "timeout-countdown",
+        "(continuing with '{ $default }' in { $seconds }s…)",
+    ),
+    ("validator-invalid", "Please answer with { $constraint }."),
+    ("length-exactly", "exactly { $min } characters"),
+    ("length-between", "between { $min } and { $max } characters"),
+    ("length-at-most", "at most { $max } characters"),
+    ("length-at-least", "at least { $min } characters"),
+    ("alphanumeric", "letters and digits only"),
+    ("constraint-natural", "a non-negative whole number"),
+    ("constraint-integer", "a whole number"),
+    ("constraint-number", "a number"),
+    ("constraint-bool", "true or false"),
+    ("constraint-char", "a single character"),
+    ("constraint-value", "a valid value"),
+    ("dry-run-type", "<{ $constraint }>"),
+    ("dry-run-conditional", "(if its condition holds)"),
+    ("error-label", "Error:"),
+    ("redacted", "«redacted»"),
+];
+
+impl Default for Messages {
+    fn default() -> Self {
+        Self::english()
+    }
+}
+
+impl Messages {
+    pub fn english() -> Self {
+        Self {
+            texts: ENGLISH
+                .iter()
+                .map(|(id, text)| (id.to_string(), text.to_string()))
+                .collect(),
+        }
+    }
+
+    /// Replaces the text of the message `id`.
+    ///
+    /// The `confirm-yes`, `confirm-no` and `multi-select-keywords` messages
+    /// are lists separated by commas: those of the answers accepted as yes or
+    /// no, and those of the keywords selecting all, none or the inverse of
+    /// the checked choices.
+    pub fn set(mut self, id: impl Into<String>, text: impl Into<String>) -> Self {
+        self.texts.insert(id.into(), text.into());
+        self
+    }
+
+    /// Replaces the texts of the messages defined in `source`, a Fluent
+    /// resource made of simple `id = text` messages and comments.
+    #[cfg(feature = "fluent")]
+    pub fn from_ftl(mut self, source: &str) -> std::io::Result<Self> {
+        for (number, line) in source.lines().enumerate() {
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (id, text) = line.split_once('=').ok_or_else(|| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("line {}: expected `id = text`", number + 1),
+                )
+            })?;
+
+            self.texts
+                .insert(id.trim().to_owned(), text.trim().to_owned());
+        }
+
+        Ok(self)
+    }
+
+    /// Returns the text of the message `id`, with its placeables replaced by
+    /// the `arguments` in a single pass, so that the placeables within their
+    /// values are kept verbatim.
+    pub(crate) fn format(&self, id: &str, arguments: &[(&str, &dyn fmt::Display)]) -> String {
+        let mut rest = self.texts.get(id).map_or(id, String::as_str);
+        let mut text = String::with_capacity(rest.len());
+
+        while let Some(start) = rest.find('{') {
+            text.push_str(&rest[..start]);
+            rest = &rest[start..];
+
+            let placeable = rest.find('}').and_then(|end| {
+                let name = rest[1..end].trim().strip_prefix('$')?;
+
+                arguments
+                    .iter()
+                    .find(|(argument, _)| *argument == name)
+                    .map(|(_, value)| (end, value))
+            });
+
+            match placeable {
+                Some((end, value)) => {
+                    text.push_str(&value.to_string());
+                    rest = &rest[end + 1..];
+                }
+                None => {
+                    text.push('{');
+                    rest = &rest[1..];
+                }
+            }
+        }
+
+        text.push_str(rest);
+        text
+    }
+
+    /// Returns the items of the list message `id`.
+    pub(crate) fn list(&self, id: &str) -> Vec<String> {
+        self.format(id, &[])
+            .split(',')
+            .map(|item| item.trim().to_owned())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_should_replace_the_placeables() {
+        let messages = Messages::english().set("select-prompt", "Choisissez de 1 à {$count} :");
+
+        assert_eq!(
+            messages.format("select-prompt", &[("count", &3)]),
+            "Choisissez de 1 à 3 :"
+        );
+        assert_eq!(
+            Messages::english().format("select-invalid", &[("count", &3)]),
+            "Please choose a number between 1 and 3."
+        );
+    }
+
+    #[test]
+    fn format_should_keep_the_placeables_within_the_arguments() {
+        let messages = Messages::english().set("greeting", "{ $name } has { $count } { unknown }");
+
+        assert_eq!(
+            messages.format("greeting", &[("name", &"{ $count }"), ("count", &3)]),
+            "{ $count } has 3 { unknown }"
+        );
+    }
+
+    #[cfg(feature = "fluent")]
+    #[test]
+    fn from_ftl_should_replace_the_defined_messages() {
+        let messages = Messages::english()
+            .from_ftl("# French\nconfirm-invalid = Veuillez répondre oui ou non.\n")
+            .expect("from_ftl() should not fail");
+
+        assert_eq!(
+            messages.format("confirm-invalid", &[]),
+            "Veuillez répondre oui ou non."
+        );
+        assert_eq!(messages.format("confirm-hint-yes", &[]), "[Y/n]");
+    }
+}
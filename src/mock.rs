@@ -0,0 +1,125 @@
+use crate::PromptBackend;
+use std::{collections::VecDeque, io};
+
+/// A [`PromptBackend`] answering with scripted answers and recording what gets
+/// rendered, to test code asking questions.
+#[derive(Clone, Debug, Default)]
+pub struct MockBackend {
+    answers: VecDeque<String>,
+    prompts: Vec<String>,
+    /// What was rendered, interleaved with the answers as they were typed.
+    transcript: String,
+}
+
+impl MockBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enqueues `answer`, given to the next question left unanswered; once the
+    /// answers run out, the questions get no answer, as at the end of input.
+    pub fn answer(mut self, answer: impl Into<String>) -> Self {
+        self.answers.push_back(answer.into());
+        self
+    }
+
+    /// Returns the frames rendered so far, e.g. the questions, in order.
+    pub fn prompts(&self) -> &[String] {
+        &self.prompts
+    }
+
+    /// Returns the whole session as a terminal would display it, the answers
+    /// being echoed, for snapshot tests; control characters other than line
+    /// feeds are escaped, e.g. `\u{1b}[2m` for a styling escape sequence.
+    pub fn snapshot(&self) -> String {
+        let mut snapshot = String::with_capacity(self.transcript.len());
+
+        for c in self.transcript.chars() {
+            if c.is_control() && c != '\n' {
+                snapshot.extend(c.escape_default());
+            } else {
+                snapshot.push(c);
+            }
+        }
+
+        snapshot
+    }
+
+    /// Returns the number of enqueued answers not given yet.
+    pub fn remaining(&self) -> usize {
+        self.answers.len()
+    }
+}
+
+impl PromptBackend for MockBackend {
+    fn render(&mut self, frame: &[u8]) -> io::Result<()> {
+        let frame = String::from_utf8_lossy(frame).into_owned();
+
+        self.transcript.push_str(&frame);
+        self.prompts.push(frame);
+        Ok(())
+    }
+
+    fn read_line(&mut self, line: &mut String) -> io::Result<usize> {
+        let Some(answer) = self.answers.pop_front() else {
+            return Ok(0);
+        };
+
+        line.push_str(&answer);
+        line.push('\n');
+        self.transcript.push_str(&answer);
+        self.transcript.push('\n');
+
+        Ok(answer.len() + 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Question;
+
+    #[test]
+    fn mock_backend_should_record_the_prompts_in_order() {
+        let mut backend = MockBackend::new().answer("maybe").answer("no");
+        let result = Question::with_backend(&mut backend)
+            .confirm("Sure?", true)
+            .expect("confirm() should not fail");
+
+        assert_eq!(result, Some(false));
+        assert_eq!(
+            backend.prompts(),
+            ["Sure? [Y/n] ", "Please answer yes or no.\nSure? [Y/n] "]
+        );
+        assert_eq!(backend.remaining(), 0);
+    }
+
+    #[test]
+    fn snapshot_should_render_the_session_with_visible_escape_sequences() {
+        let mut backend = MockBackend::new().answer("").answer("Alice");
+
+        Question::with_backend(&mut backend)
+            .ansi(true)
+            .echo_answers(false)
+            .ask_validated("Your name?", |answer| match answer {
+                "" => Err(String::from("Please answer.")),
+                _ => Ok(()),
+            })
+            .expect("ask_validated() should not fail");
+
+        assert_eq!(
+            backend.snapshot(),
+            "Your name? \n\\u{1b}[1A\\r\\u{1b}[JPlease answer.\nYour name? Alice\n"
+        );
+    }
+
+    #[test]
+    fn mock_backend_should_give_no_answer_once_the_answers_run_out() {
+        let mut backend = MockBackend::new();
+        let result = Question::with_backend(&mut backend)
+            .ask("Your name?")
+            .expect("ask() should not fail");
+
+        assert!(result.is_none());
+    }
+}
@@ -0,0 +1,219 @@
+use std::time::{Duration, Instant};
+
+/// Observes the questions being asked, e.g. to pipe anonymized metrics into
+/// the telemetry of an application; the answers are never passed to it.
+///
+/// Every callback is given the `context` of the question, and does nothing by
+/// default.
+pub trait PromptObserver {
+    fn question_shown(&mut self, question: &str, context: &QuestionContext) {
+        let _ = (question, context);
+    }
+
+    /// Called when an answer to `question` was refused with `message`.
+    fn validation_failed(&mut self, question: &str, message: &str, context: &QuestionContext) {
+        let _ = (question, message, context);
+    }
+
+    /// Called when `question` was answered, `elapsed` after it was shown.
+    fn answered(&mut self, question: &str, elapsed: Duration, context: &QuestionContext) {
+        let _ = (question, elapsed, context);
+    }
+
+    /// Called when `question` was left unanswered, e.g. at EOF.
+    fn cancelled(&mut self, question: &str, context: &QuestionContext) {
+        let _ = (question, context);
+    }
+}
+
+/// What a form tells about the question it asks, e.g. to filter the events of
+/// a large wizard by tag; empty for the questions asked outside forms.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct QuestionContext {
+    /// The id of the question, as given to [`Form::field`](crate::Form::field).
+    pub id: String,
+    /// The tags of the question, as set by [`Form::tag`](crate::Form::tag).
+    pub tags: Vec<String>,
+    /// The metadata of the question, as set by [`Form::meta`](crate::Form::meta).
+    pub metadata: Vec<(String, String)>,
+}
+
+/// The observer of a question, along with the question it is shown.
+pub(crate) struct Observer {
+    inner: Option<Box<dyn PromptObserver + Send>>,
+    question: String,
+    context: QuestionContext,
+    shown_at: Instant,
+    /// The nesting of the questions asking again until their answer is valid,
+    /// which tell about the valid answer only.
+    pub(crate) validating: usize,
+}
+
+impl Default for Observer {
+    fn default() -> Self {
+        Self {
+            inner: None,
+            question: String::new(),
+            context: QuestionContext::default(),
+            shown_at: Instant::now(),
+            validating: 0,
+        }
+    }
+}
+
+impl Observer {
+    pub(crate) fn new(inner: impl PromptObserver + Send + 'static) -> Self {
+        Self {
+            inner: Some(Box::new(inner)),
+            ..Self::default()
+        }
+    }
+
+    /// Tells the context of the question of a form asked next, until
+    /// [`Observer::leave`].
+    pub(crate) fn enter(&mut self, id: &str, tags: &[String], metadata: &[(String, String)]) {
+        if self.inner.is_some() {
+            self.context = QuestionContext {
+                id: id.to_owned(),
+                tags: tags.to_vec(),
+                metadata: metadata.to_vec(),
+            };
+        }
+    }
+
+    pub(crate) fn leave(&mut self) {
+        self.context = QuestionContext::default();
+    }
+
+    pub(crate) fn shown(&mut self, question: &str) {
+        if let Some(inner) = &mut self.inner {
+            self.question.clear();
+            self.question.push_str(question.trim_end());
+            self.shown_at = Instant::now();
+            inner.question_shown(&self.question, &self.context);
+        }
+    }
+
+    pub(crate) fn failed(&mut self, message: &str) {
+        if let Some(inner) = &mut self.inner {
+            inner.validation_failed(&self.question, message, &self.context);
+        }
+    }
+
+    pub(crate) fn answered(&mut self) {
+        if self.validating > 0 {
+            return;
+        }
+
+        if let Some(inner) = &mut self.inner {
+            inner.answered(&self.question, self.shown_at.elapsed(), &self.context);
+        }
+    }
+
+    pub(crate) fn cancelled(&mut self) {
+        if let Some(inner) = &mut self.inner {
+            inner.cancelled(&self.question, &self.context);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Form, Question};
+    use std::{
+        io,
+        sync::{Arc, Mutex},
+    };
+
+    #[derive(Clone, Default)]
+    struct Events(Arc<Mutex<Vec<String>>>);
+
+    impl PromptObserver for Events {
+        fn question_shown(&mut self, question: &str, _: &QuestionContext) {
+            self.0
+                .lock()
+                .expect("lock() should not fail")
+                .push(format!("shown {question}"));
+        }
+
+        fn validation_failed(&mut self, question: &str, message: &str, _: &QuestionContext) {
+            self.0
+                .lock()
+                .expect("lock() should not fail")
+                .push(format!("failed {question}: {message}"));
+        }
+
+        fn answered(&mut self, question: &str, _: Duration, context: &QuestionContext) {
+            let mut event = format!("answered {question}");
+
+            if !context.id.is_empty() {
+                event.push_str(&format!(" [{}]", context.id));
+            }
+
+            for tag in &context.tags {
+                event.push_str(&format!(" #{tag}"));
+            }
+
+            for (key, value) in &context.metadata {
+                event.push_str(&format!(" {key}={value}"));
+            }
+
+            self.0.lock().expect("lock() should not fail").push(event);
+        }
+
+        fn cancelled(&mut self, question: &str, _: &QuestionContext) {
+            self.0
+                .lock()
+                .expect("lock() should not fail")
+                .push(format!("cancelled {question}"));
+        }
+    }
+
+    #[test]
+    fn observer_should_be_told_about_the_questions() {
+        let events = Events::default();
+        let mut question =
+            Question::new("maybe\ny\n".as_bytes(), io::sink()).observer(events.clone());
+
+        question
+            .confirm("Deploy?", true)
+            .expect("confirm() should not fail");
+        question.ask("Why?").expect("ask() should not fail");
+
+        assert_eq!(
+            *events.0.lock().expect("lock() should not fail"),
+            [
+                "shown Deploy? [Y/n]",
+                "failed Deploy? [Y/n]: Please answer yes or no.",
+                "shown Deploy? [Y/n]",
+                "answered Deploy? [Y/n]",
+                "shown Why?",
+                "cancelled Why?",
+            ]
+        );
+    }
+
+    #[test]
+    fn observer_should_be_told_about_the_accepted_answers_of_a_form() {
+        let events = Events::default();
+
+        Form::new()
+            .field("port", "Port?")
+            .parsed::<u16>()
+            .tag("network")
+            .meta("section", "server")
+            .ask(&mut Question::new("http\n80\n".as_bytes(), io::sink()).observer(events.clone()))
+            .expect("ask() should not fail");
+
+        assert_eq!(
+            *events.0.lock().expect("lock() should not fail"),
+            [
+                "shown Port?",
+                "failed Port?: 'http' is not a valid answer.",
+                "shown Port?",
+                "answered Port? [port] #network section=server",
+            ]
+        );
+    }
+}
@@ -0,0 +1,266 @@
+use crate::Question;
+use std::{io, ops::Range};
+
+/// The lines of context around the changes of a diff.
+const CONTEXT: usize = 3;
+/// The most pairs of lines compared to find the changes of a diff, bounding
+/// its memory; beyond, the changed lines are all removed then added.
+const MAX_COMPARED: usize = 1 << 20;
+
+/// What to do with a file about to be overwritten, as answered to
+/// [`Question::confirm_overwrite`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Overwrite {
+    Overwrite,
+    /// Keeps the existing file, which is also the answer when the contents
+    /// are the same.
+    Skip,
+    /// Stops generating any further file.
+    Abort,
+}
+
+impl<R: io::BufRead, W: io::Write> Question<R, W> {
+    /// Displays the unified diff turning the `existing` contents of the file
+    /// at `path` into the `proposed` ones and asks whether to overwrite it,
+    /// skip it or abort, or to display the full proposed contents first.
+    pub fn confirm_overwrite(
+        &mut self,
+        path: &str,
+        existing: &str,
+        proposed: &str,
+    ) -> io::Result<Option<Overwrite>> {
+        if existing == proposed {
+            return Ok(Some(Overwrite::Skip));
+        }
+
+        let question = self.messages.format("overwrite-prompt", &[("path", &path)]);
+        let keywords = self.messages.list("overwrite-keywords");
+        let keyword = |index: usize| keywords.get(index).map_or("", String::as_str);
+
+        self.show_code(
+            &format!(
+                "--- {path}\n+++ {path}\n{}",
+                unified_diff(existing, proposed)
+            ),
+            "diff",
+        )?;
+
+        self.validating(|this| loop {
+            let Some(answer) = this.ask(question.as_str())? else {
+                return Ok(None);
+            };
+
+            if this.matching.eq(&answer, keyword(0)) {
+                return Ok(Some(Overwrite::Overwrite));
+            } else if this.matching.eq(&answer, keyword(1)) {
+                return Ok(Some(Overwrite::Skip));
+            } else if this.matching.eq(&answer, keyword(3)) {
+                return Ok(Some(Overwrite::Abort));
+            } else if this.matching.eq(&answer, keyword(2)) {
+                this.show_code(proposed, "")?;
+                continue;
+            }
+
+            let message = this.messages.format("overwrite-invalid", &[]);
+
+            this.write_error(&message)?;
+        })
+    }
+}
+
+/// A line of a diff.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Edit<'a> {
+    Keep(&'a str),
+    Remove(&'a str),
+    Add(&'a str),
+}
+
+/// Returns the hunks of the unified diff turning `old` into `new`, with
+/// their `@@` headers but without the file headers.
+fn unified_diff(old: &str, new: &str) -> String {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    let edits = edits(&old, &new);
+
+    let mut hunks: Vec<Range<usize>> = Vec::new();
+
+    for (index, _) in edits
+        .iter()
+        .enumerate()
+        .filter(|(_, edit)| !matches!(edit, Edit::Keep(_)))
+    {
+        let start = index.saturating_sub(CONTEXT);
+        let end = (index + 1 + CONTEXT).min(edits.len());
+
+        match hunks.last_mut() {
+            Some(hunk) if start <= hunk.end => hunk.end = end,
+            _ => hunks.push(start..end),
+        }
+    }
+
+    let mut diff = String::new();
+    let (mut old_line, mut new_line, mut position) = (0, 0, 0);
+
+    for hunk in hunks {
+        for edit in &edits[position..hunk.start] {
+            old_line += usize::from(!matches!(edit, Edit::Add(_)));
+            new_line += usize::from(!matches!(edit, Edit::Remove(_)));
+        }
+
+        let edits = &edits[hunk.clone()];
+        let old_count = edits
+            .iter()
+            .filter(|edit| !matches!(edit, Edit::Add(_)))
+            .count();
+        let new_count = edits
+            .iter()
+            .filter(|edit| !matches!(edit, Edit::Remove(_)))
+            .count();
+        let start = |line: usize, count: usize| line + usize::from(count > 0);
+
+        diff.push_str(&format!(
+            "@@ -{},{old_count} +{},{new_count} @@\n",
+            start(old_line, old_count),
+            start(new_line, new_count)
+        ));
+
+        for edit in edits {
+            let (marker, line) = match edit {
+                Edit::Keep(line) => (' ', line),
+                Edit::Remove(line) => ('-', line),
+                Edit::Add(line) => ('+', line),
+            };
+
+            diff.push(marker);
+            diff.push_str(line);
+            diff.push('\n');
+        }
+
+        old_line += old_count;
+        new_line += new_count;
+        position = hunk.end;
+    }
+
+    diff
+}
+
+/// Returns the edits turning `old` into `new` along their longest common
+/// subsequence, removals first, keeping their common first and last lines.
+fn edits<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<Edit<'a>> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let mut edits: Vec<Edit<'a>> = old[..prefix].iter().copied().map(Edit::Keep).collect();
+    let (middle_old, middle_new) = (
+        &old[prefix..old.len() - suffix],
+        &new[prefix..new.len() - suffix],
+    );
+
+    match middle_old.len().saturating_mul(middle_new.len()) > MAX_COMPARED {
+        true => {
+            edits.extend(middle_old.iter().copied().map(Edit::Remove));
+            edits.extend(middle_new.iter().copied().map(Edit::Add));
+        }
+        false => edits.extend(changes(middle_old, middle_new)),
+    }
+
+    edits.extend(old[old.len() - suffix..].iter().copied().map(Edit::Keep));
+    edits
+}
+
+/// Returns the edits turning `old` into `new` along their longest common
+/// subsequence, removals first.
+fn changes<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<Edit<'a>> {
+    let mut common = vec![vec![0_usize; new.len() + 1]; old.len() + 1];
+
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = match old[i] == new[j] {
+                true => common[i + 1][j + 1] + 1,
+                false => common[i + 1][j].max(common[i][j + 1]),
+            };
+        }
+    }
+
+    let mut edits = Vec::with_capacity(old.len().max(new.len()));
+    let (mut i, mut j) = (0, 0);
+
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            edits.push(Edit::Keep(old[i]));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || common[i + 1][j] >= common[i][j + 1]) {
+            edits.push(Edit::Remove(old[i]));
+            i += 1;
+        } else {
+            edits.push(Edit::Add(new[j]));
+            j += 1;
+        }
+    }
+
+    edits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXISTING: &str = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\n";
+    const PROPOSED: &str = "a\nb\nc\nd\nE\nf\ng\nh\ni\nj\nk\n";
+
+    #[test]
+    fn unified_diff_should_render_the_hunks_with_their_context() {
+        assert_eq!(
+            unified_diff(EXISTING, PROPOSED),
+            "@@ -2,9 +2,10 @@\n b\n c\n d\n-e\n+E\n f\n g\n h\n i\n j\n+k\n"
+        );
+        assert_eq!(
+            unified_diff("1\n2\n3\n4\n5\n6\n7\n8\n9\n", "0\n1\n2\n3\n4\n5\n6\n7\n8\n"),
+            "@@ -1,3 +1,4 @@\n+0\n 1\n 2\n 3\n@@ -6,4 +7,3 @@\n 6\n 7\n 8\n-9\n"
+        );
+    }
+
+    #[test]
+    fn unified_diff_should_replace_the_changed_lines_of_large_files_at_once() {
+        let old: String = (0..1200).map(|line| format!("{line}\n")).collect();
+        let new: String = (0..1200).map(|line| format!("{}\n", line * 2)).collect();
+        let diff = unified_diff(&old, &new);
+
+        assert!(diff.starts_with("@@ -1,1200 +1,1200 @@\n 0\n-1\n-2\n"));
+        assert!(diff.contains("-1199\n+2\n+4\n"));
+    }
+
+    #[test]
+    fn confirm_overwrite_should_show_the_full_file_before_asking_again() {
+        let mut output = Vec::new();
+        let result = Question::new("maybe\nf\no\n".as_bytes(), &mut output)
+            .confirm_overwrite("x.txt", "a\n", "b\n")
+            .expect("confirm_overwrite() should not fail");
+
+        assert_eq!(result, Some(Overwrite::Overwrite));
+        assert_eq!(
+            String::from_utf8(output).expect("from_utf8() should not fail"),
+            "--- x.txt\n+++ x.txt\n@@ -1,1 +1,1 @@\n-a\n+b\n\
+             Overwrite x.txt? [o/s/f/a] \
+             Please answer o to overwrite, s to skip, f to show the full file or a to abort.\n\
+             Overwrite x.txt? [o/s/f/a] b\nOverwrite x.txt? [o/s/f/a] "
+        );
+    }
+
+    #[test]
+    fn confirm_overwrite_should_skip_the_same_contents_without_asking() {
+        let mut output = Vec::new();
+        let result = Question::new(io::empty(), &mut output)
+            .confirm_overwrite("x.txt", "a\n", "a\n")
+            .expect("confirm_overwrite() should not fail");
+
+        assert_eq!(result, Some(Overwrite::Skip));
+        assert!(output.is_empty());
+    }
+}
@@ -0,0 +1,84 @@
+use crate::{Answers, Question};
+use std::io;
+
+/// Questions enqueued by different parts of an application, e.g. while
+/// planning an operation, then asked at once in priority order by
+/// [`QuestionQueue::drain`] rather than in the middle of the operation.
+#[derive(Clone, Debug, Default)]
+pub struct QuestionQueue {
+    questions: Vec<(i32, String, String)>,
+}
+
+impl QuestionQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enqueues `question`, whose answer is stored as `id`; the questions of
+    /// higher priority are asked first, those of a same priority in order.
+    pub fn enqueue(
+        &mut self,
+        id: impl Into<String>,
+        question: impl Into<String>,
+        priority: i32,
+    ) -> &mut Self {
+        self.questions.push((priority, id.into(), question.into()));
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.questions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.questions.is_empty()
+    }
+
+    /// Asks the enqueued questions, emptying the queue, and collects the
+    /// answers by id, stopping at EOF with `None`.
+    pub fn drain<R: io::BufRead, W: io::Write>(
+        &mut self,
+        question: &mut Question<R, W>,
+    ) -> io::Result<Option<Answers>> {
+        let mut questions = std::mem::take(&mut self.questions);
+
+        questions.sort_by(|(a, _, _), (b, _, _)| b.cmp(a));
+
+        let questions: Vec<(&str, &str)> = questions
+            .iter()
+            .map(|(_, id, text)| (id.as_str(), text.as_str()))
+            .collect();
+
+        question.ask_many(&questions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drain_should_ask_the_questions_in_priority_order() {
+        let mut queue = QuestionQueue::new();
+        let mut output = Vec::new();
+
+        queue
+            .enqueue("name", "Name?", 0)
+            .enqueue("overwrite", "Overwrite?", 10)
+            .enqueue("port", "Port?", 0);
+
+        let answers = queue
+            .drain(&mut Question::new(
+                "y\nAlice\n8080\n".as_bytes(),
+                &mut output,
+            ))
+            .expect("drain() should not fail")
+            .expect("drain() should return the answers");
+
+        assert!(queue.is_empty());
+        assert_eq!(answers.get("overwrite"), Some("y"));
+        assert_eq!(answers.get("name"), Some("Alice"));
+        assert_eq!(answers.get("port"), Some("8080"));
+        assert_eq!(output, b"Overwrite? Name? Port? ");
+    }
+}
@@ -0,0 +1,184 @@
+//! The recording of a prompt session in the asciinema cast format (v2): a
+//! JSON header, then one `[seconds, "o" or "i", data]` event per line.
+
+use crate::json;
+use std::{
+    io,
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+/// The height of the recorded terminal, which the crate doesn't know.
+const HEIGHT: usize = 24;
+
+/// Records the output and the answers of a session; since the recording only
+/// serves replaying the session, failing to write it is ignored.
+pub(crate) struct Recorder {
+    writer: Box<dyn io::Write + Send>,
+    started: Instant,
+}
+
+impl Recorder {
+    pub(crate) fn new(mut writer: Box<dyn io::Write + Send>, width: usize) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs());
+        let _ = writeln!(
+            writer,
+            "{{\"version\": 2, \"width\": {width}, \"height\": {HEIGHT}, \"timestamp\": {timestamp}}}"
+        );
+
+        Self {
+            writer,
+            started: Instant::now(),
+        }
+    }
+
+    /// Records the bytes written to the terminal.
+    pub(crate) fn output(&mut self, bytes: &[u8]) {
+        self.event("o", &String::from_utf8_lossy(bytes));
+    }
+
+    /// Records an answer, as typed.
+    pub(crate) fn input(&mut self, answer: &str) {
+        self.event("i", &format!("{answer}\n"));
+    }
+
+    /// Records input streamed as is, e.g. by [`Question::ask_to_writer`].
+    ///
+    /// [`Question::ask_to_writer`]: crate::Question::ask_to_writer
+    pub(crate) fn stream(&mut self, bytes: &[u8]) {
+        self.event("i", &String::from_utf8_lossy(bytes));
+    }
+
+    /// Records a marker labelled with the `id`, `tags` and `metadata` of the
+    /// question of a form asked next, e.g. `port #network section=server`.
+    pub(crate) fn marker(&mut self, id: &str, tags: &[String], metadata: &[(String, String)]) {
+        let mut label = id.to_owned();
+
+        for tag in tags {
+            label.push_str(&format!(" #{tag}"));
+        }
+
+        for (key, value) in metadata {
+            label.push_str(&format!(" {key}={value}"));
+        }
+
+        self.event("m", &label);
+    }
+
+    fn event(&mut self, kind: &str, data: &str) {
+        let time = self.started.elapsed().as_secs_f64();
+        let _ = writeln!(
+            self.writer,
+            "[{time:.6}, \"{kind}\", \"{}\"]",
+            json::escape(data)
+        );
+        let _ = self.writer.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Form, Question};
+    use std::{
+        io,
+        sync::{Arc, Mutex},
+    };
+
+    /// A cast whose contents can be read once recorded.
+    #[derive(Clone, Default)]
+    struct Cast(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Cast {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().expect("lock() should not fail").write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn record_should_write_the_session_as_an_asciinema_cast() {
+        let cast = Cast::default();
+        let mut question =
+            Question::new("Alice\nhunter2\nhunter2\n".as_bytes(), Vec::new()).record(cast.clone());
+
+        question.ask("Your name?").expect("ask() should not fail");
+        question
+            .ask_twice("Password?", "Again?")
+            .expect("ask_twice() should not fail");
+
+        let cast = String::from_utf8(cast.0.lock().expect("lock() should not fail").clone())
+            .expect("from_utf8() should not fail");
+        let lines: Vec<&str> = cast.lines().collect();
+        let events: Vec<&str> = lines[1..]
+            .iter()
+            .map(|line| &line[line.find(',').unwrap_or_default()..])
+            .collect();
+
+        assert!(lines[0].starts_with("{\"version\": 2, \"width\": 80, \"height\": 24, "));
+        assert_eq!(
+            events,
+            [
+                ", \"o\", \"Your name? \"]",
+                ", \"i\", \"Alice\\n\"]",
+                ", \"o\", \"Password? \"]",
+                ", \"i\", \"«redacted»\\n\"]",
+                ", \"o\", \"Again? \"]",
+                ", \"i\", \"«redacted»\\n\"]",
+            ]
+        );
+    }
+
+    #[test]
+    fn record_should_write_the_answers_read_into_buffers_and_writers() {
+        let cast = Cast::default();
+        let mut question =
+            Question::new("Alice\nrest\n".as_bytes(), io::sink()).record(cast.clone());
+        let mut answer = String::new();
+
+        question
+            .ask_into("Your name?", &mut answer)
+            .expect("ask_into() should not fail");
+        question
+            .ask_to_writer("Notes?", &mut io::sink())
+            .expect("ask_to_writer() should not fail");
+
+        let cast = String::from_utf8(cast.0.lock().expect("lock() should not fail").clone())
+            .expect("from_utf8() should not fail");
+        let events: Vec<&str> = cast
+            .lines()
+            .skip(1)
+            .map(|line| &line[line.find(',').unwrap_or_default()..])
+            .collect();
+
+        assert_eq!(
+            events,
+            [
+                ", \"o\", \"Your name? \"]",
+                ", \"i\", \"Alice\\n\"]",
+                ", \"o\", \"Notes? \"]",
+                ", \"i\", \"rest\\n\"]",
+            ]
+        );
+    }
+
+    #[test]
+    fn record_should_mark_the_questions_of_a_form() {
+        let cast = Cast::default();
+
+        Form::new()
+            .field("port", "Port?")
+            .tag("network")
+            .meta("section", "server")
+            .ask(&mut Question::new("80\n".as_bytes(), io::sink()).record(cast.clone()))
+            .expect("ask() should not fail");
+
+        let cast = String::from_utf8(cast.0.lock().expect("lock() should not fail").clone())
+            .expect("from_utf8() should not fail");
+
+        assert!(cast.contains(", \"m\", \"port #network section=server\"]\n"));
+    }
+}
@@ -0,0 +1,255 @@
+use crate::{theme::Prompt, Question, QuestionError};
+use std::{io, thread, time::Duration};
+
+/// The longest delay of [`Lockout::Backoff`].
+const MAX_DELAY: Duration = Duration::from_secs(60 * 60);
+
+/// What happens once an answer was refused too many times, e.g. a PIN.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Lockout {
+    /// Waits `delay` after the refusals following the first `after` ones,
+    /// doubling the delay each time up to an hour.
+    Backoff { after: usize, delay: Duration },
+    /// Fails with [`QuestionError::LockedOut`], wrapped in an error of kind
+    /// [`io::ErrorKind::PermissionDenied`], after `after` refusals.
+    Abort { after: usize },
+}
+
+impl<R: io::BufRead, W: io::Write> Question<R, W> {
+    /// Asks a question twice, e.g. for a new password, until both answers are
+    /// the same, and returns the answer.
+    pub fn ask_twice(
+        &mut self,
+        question: impl Into<String>,
+        confirmation: impl Into<String>,
+    ) -> io::Result<Option<String>> {
+        let question = question.into();
+        let confirmation = confirmation.into();
+
+        self.validating(|this| loop {
+            let Some(answer) = this.ask_as(Prompt::Secret, question.as_str())? else {
+                return Ok(None);
+            };
+            let Some(again) = this.ask_as(Prompt::Secret, confirmation.as_str())? else {
+                wipe(answer);
+
+                return Ok(None);
+            };
+
+            let matching = constant_time_eq(answer.as_bytes(), again.as_bytes());
+
+            wipe(again);
+
+            if matching {
+                return Ok(Some(answer));
+            }
+
+            wipe(answer);
+
+            let message = this.messages.format("mismatch", &[]);
+
+            this.write_error(&message)?;
+        })
+    }
+
+    /// Applies the lockout policy once an answer was refused `failures` times.
+    pub(crate) fn lock_out(&mut self, failures: usize) -> io::Result<()> {
+        match self.lockout {
+            Some(Lockout::Backoff { after, delay }) if failures > after => {
+                let factor = u32::try_from(failures - after - 1)
+                    .ok()
+                    .and_then(|exponent| 2_u32.checked_pow(exponent))
+                    .unwrap_or(u32::MAX);
+
+                thread::sleep(delay.saturating_mul(factor).min(MAX_DELAY));
+            }
+            Some(Lockout::Abort { after }) if failures >= after => {
+                return Err(io::Error::new(
+                    io::ErrorKind::PermissionDenied,
+                    QuestionError::LockedOut,
+                ));
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Asks to type `phrase`, e.g. the name of what is about to be deleted, to
+    /// confirm a dangerous action, and tells whether it was typed exactly; the
+    /// phrases mistyped in a row count against the lockout policy.
+    pub fn confirm_phrase(
+        &mut self,
+        question: impl Into<String>,
+        phrase: &str,
+    ) -> io::Result<Option<bool>> {
+        let Some(answer) = self.ask_as(Prompt::Confirm, question)? else {
+            return Ok(None);
+        };
+        let matching = constant_time_eq(answer.as_bytes(), phrase.as_bytes());
+
+        wipe(answer);
+
+        match matching {
+            true => self.mistyped_phrases = 0,
+            false => {
+                self.mistyped_phrases += 1;
+                self.lock_out(self.mistyped_phrases)?;
+            }
+        }
+
+        Ok(Some(matching))
+    }
+}
+
+/// Frees `buffer`, overwriting it with zeros first with the `zeroize` feature
+/// so that the secret it holds doesn't linger in memory.
+pub(crate) fn wipe(buffer: String) {
+    #[cfg(feature = "zeroize")]
+    {
+        let mut bytes = buffer.into_bytes();
+
+        bytes.resize(bytes.capacity(), 0);
+
+        for byte in &mut bytes {
+            // SAFETY: `byte` is a valid and aligned reference to an `u8`.
+            unsafe { std::ptr::write_volatile(byte, 0) };
+        }
+
+        std::sync::atomic::compiler_fence(std::sync::atomic::Ordering::SeqCst);
+    }
+
+    #[cfg(not(feature = "zeroize"))]
+    drop(buffer);
+}
+
+/// A string wiped like by [`wipe`] once dropped, e.g. an answer kept among
+/// the [`Answers`](crate::Answers) of a form, which may be sensitive.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct Wiped(pub(crate) String);
+
+impl Drop for Wiped {
+    fn drop(&mut self) {
+        wipe(std::mem::take(&mut self.0));
+    }
+}
+
+/// Compares `a` and `b` in a time depending on their lengths only, so that
+/// comparing secrets doesn't tell how much of them match.
+pub(crate) fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    let difference = a
+        .iter()
+        .zip(b)
+        .fold(a.len() ^ b.len(), |difference, (a, b)| {
+            difference | usize::from(a ^ b)
+        });
+
+    difference == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn constant_time_eq_should_compare_the_bytes() {
+        assert!(constant_time_eq(b"hunter2", b"hunter2"));
+        assert!(!constant_time_eq(b"hunter2", b"hunter3"));
+        assert!(!constant_time_eq(b"hunter2", b"hunter"));
+    }
+
+    #[test]
+    fn ask_twice_should_ask_again_until_both_answers_match() {
+        let mut output = Vec::new();
+        let result = Question::new(
+            "hunter2\nhunter3\nhunter2\nhunter2\n".as_bytes(),
+            &mut output,
+        )
+        .ask_twice("Password?", "Again?")
+        .expect("ask_twice() should not fail");
+
+        assert_eq!(result, Some(String::from("hunter2")));
+        assert_eq!(
+            output,
+            b"Password? Again? The answers don't match.\nPassword? Again? "
+        );
+    }
+
+    #[test]
+    fn ask_twice_should_mask_the_echoed_answers() {
+        let mut output = Vec::new();
+
+        Question::new("hunter2\nhunter2\n".as_bytes(), &mut output)
+            .ansi(true)
+            .ask_twice("Password?", "Again?")
+            .expect("ask_twice() should not fail");
+        let output = String::from_utf8(output).expect("from_utf8() should not fail");
+
+        assert!(output.contains("Password? … \x1b[36m••••••••\x1b[39m\n"));
+        assert!(!output.contains("hunter2"));
+    }
+
+    #[test]
+    fn ask_validated_should_abort_after_too_many_refused_answers() {
+        let error = Question::new("1\n2\n3\n".as_bytes(), io::sink())
+            .lockout(Lockout::Abort { after: 2 })
+            .ask_validated("PIN?", |_| Err(String::from("Wrong PIN.")))
+            .expect_err("ask_validated() should fail");
+
+        assert_eq!(error.kind(), io::ErrorKind::PermissionDenied);
+        assert!(matches!(
+            QuestionError::from(error),
+            QuestionError::LockedOut
+        ));
+    }
+
+    #[test]
+    fn confirm_phrase_should_abort_after_too_many_mistyped_phrases() {
+        let mut question =
+            Question::new("my-rep\nmy-repo\nmy-rep\nmy-rep\n".as_bytes(), io::sink())
+                .lockout(Lockout::Abort { after: 2 });
+
+        for _ in 0..3 {
+            question
+                .confirm_phrase("Type my-repo to delete it:", "my-repo")
+                .expect("confirm_phrase() should not fail");
+        }
+        let error = question
+            .confirm_phrase("Type my-repo to delete it:", "my-repo")
+            .expect_err("confirm_phrase() should fail");
+
+        assert!(matches!(
+            QuestionError::from(error),
+            QuestionError::LockedOut
+        ));
+    }
+
+    #[test]
+    fn lock_out_should_not_overflow_after_many_refused_answers() {
+        Question::new(io::empty(), io::sink())
+            .lockout(Lockout::Backoff {
+                after: 0,
+                delay: Duration::ZERO,
+            })
+            .lock_out(usize::MAX)
+            .expect("lock_out() should not fail");
+    }
+
+    #[test]
+    fn confirm_phrase_should_tell_whether_the_phrase_was_typed() {
+        let mut question = Question::new("my-repo\nmy-rep\n".as_bytes(), io::sink());
+
+        assert_eq!(
+            question
+                .confirm_phrase("Type my-repo to delete it:", "my-repo")
+                .ok(),
+            Some(Some(true))
+        );
+        assert_eq!(
+            question
+                .confirm_phrase("Type my-repo to delete it:", "my-repo")
+                .ok(),
+            Some(Some(false))
+        );
+    }
+}
@@ -0,0 +1,666 @@
+use crate::{matching::Matching, theme::Prompt, Choice, Messages, Question};
+use std::{
+    io::{self, Write},
+    ops::{RangeBounds, RangeInclusive},
+    slice,
+};
+
+/// Lists having more options than this are laid out in columns when the
+/// labels are short enough.
+const MULTI_COLUMN_THRESHOLD: usize = 10;
+const COLUMN_GAP: usize = 2;
+
+/// The choice selected in a menu, along with its position in the menu.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Selection<T> {
+    pub index: usize,
+    pub value: T,
+}
+
+impl<R: io::BufRead, W: io::Write> Question<R, W> {
+    /// Asks to select one of `choices` from a numbered menu, working on any
+    /// reader and writer, and returns the selected one.
+    ///
+    /// The answer is either the number, the label or an alias of a choice.
+    pub fn select<T>(
+        &mut self,
+        question: impl Into<String>,
+        choices: impl IntoIterator<Item = impl Into<Choice<T>>>,
+    ) -> io::Result<Option<Selection<T>>> {
+        let mut choices: Vec<Choice<T>> = choices.into_iter().map(Into::into).collect();
+
+        writeln!(self.frame, "{}", question.into())?;
+        self.write_options(&choices, false)?;
+
+        let prompt = self
+            .messages
+            .format("select-prompt", &[("count", &choices.len())]);
+
+        self.validating(|this| loop {
+            let Some(answer) = this.ask_as(Prompt::Select, prompt.as_str())? else {
+                return Ok(None);
+            };
+
+            match find_choice(&choices, &answer, this.matching) {
+                Some(index) => match choices[index].unavailable(&this.messages) {
+                    Some(message) => this.write_error(&message)?,
+                    None => {
+                        let value = choices.swap_remove(index).value;
+
+                        return Ok(Some(Selection { index, value }));
+                    }
+                },
+                None => {
+                    let message = this
+                        .messages
+                        .format("select-invalid", &[("count", &choices.len())]);
+
+                    this.write_error(&message)?
+                }
+            }
+        })
+    }
+
+    /// Asks to select any number of `choices` from a numbered menu, working on
+    /// any reader and writer, and returns the selected ones in menu order.
+    ///
+    /// The answer lists numbers, ranges of numbers such as `5-7`, labels or
+    /// aliases separated by commas, or is one of `all`, `none` and `invert`
+    /// (the checked choices); an empty answer keeps the initially checked
+    /// choices.
+    pub fn multi_select<T>(
+        &mut self,
+        question: impl Into<String>,
+        choices: impl IntoIterator<Item = impl Into<Choice<T>>>,
+    ) -> io::Result<Option<Vec<Selection<T>>>> {
+        self.multi_select_within(question, choices, ..)
+    }
+
+    /// Works like [`Question::multi_select`], asking again until the number of
+    /// selected choices is within `count`.
+    pub fn multi_select_within<T>(
+        &mut self,
+        question: impl Into<String>,
+        choices: impl IntoIterator<Item = impl Into<Choice<T>>>,
+        count: impl RangeBounds<usize>,
+    ) -> io::Result<Option<Vec<Selection<T>>>> {
+        let choices: Vec<Choice<T>> = choices.into_iter().map(Into::into).collect();
+
+        writeln!(self.frame, "{}", question.into())?;
+        self.write_options(&choices, true)?;
+
+        let keywords = self.messages.list("multi-select-keywords");
+        let keyword = |index: usize| keywords.get(index).map_or("", String::as_str);
+        let prompt = self.messages.format(
+            "multi-select-prompt",
+            &[
+                ("count", &choices.len()),
+                ("all", &keyword(0)),
+                ("none", &keyword(1)),
+                ("invert", &keyword(2)),
+            ],
+        );
+
+        self.validating(|this| loop {
+            let Some(answer) = this.ask_as(Prompt::Select, prompt.as_str())? else {
+                return Ok(None);
+            };
+
+            let indices = match answer.as_str() {
+                "" => Ok(checked(&choices)),
+                answer if this.matching.eq(answer, keyword(0)) => Ok(available(&choices, |_| true)),
+                answer if this.matching.eq(answer, keyword(1)) => Ok(Vec::new()),
+                answer if this.matching.eq(answer, keyword(2)) => {
+                    Ok(available(&choices, |choice| !choice.checked))
+                }
+                _ => parse_selection(&choices, &answer, this.matching, &this.messages),
+            };
+
+            match indices.and_then(|indices| check_count(indices, &count, &this.messages)) {
+                Ok(indices) => return Ok(Some(take_selections(choices, &indices))),
+                Err(message) => this.write_error(&message)?,
+            }
+        })
+    }
+
+    fn write_options<T>(&mut self, choices: &[Choice<T>], checkboxes: bool) -> io::Result<()> {
+        let digits = choices.len().to_string().len();
+        let mut number = 1;
+
+        for group in choices.chunk_by(|a, b| a.group == b.group) {
+            match &group[0].group {
+                Some(header) if self.theme.high_contrast && self.decorated() => {
+                    writeln!(self.frame, "\x1b[1m{header}\x1b[0m")?
+                }
+                Some(header) => writeln!(self.frame, "{header}")?,
+                None => {}
+            }
+
+            let options: Vec<(String, bool)> = (number..)
+                .zip(group)
+                .map(|(number, choice)| {
+                    let checkbox = match (checkboxes, choice.checked) {
+                        (false, _) => "",
+                        (true, false) => "[ ] ",
+                        (true, true) => "[x] ",
+                    };
+                    let option = format!("  {number:>digits$}) {checkbox}{choice}");
+
+                    (option, choice.disabled.is_some())
+                })
+                .collect();
+
+            if group.iter().any(|choice| choice.description.is_some()) {
+                let indent = digits + if checkboxes { 8 } else { 4 };
+
+                for (option, choice) in options.iter().zip(group) {
+                    self.write_columns(slice::from_ref(option))?;
+
+                    if let Some(description) = &choice.description {
+                        writeln!(self.frame, "{:indent$}{description}", "")?;
+                    }
+                }
+            } else {
+                self.write_columns(&options)?;
+            }
+
+            number += group.len();
+        }
+
+        Ok(())
+    }
+
+    /// Writes the options, dimming the disabled ones unless the theme has a
+    /// high contrast, in as many columns as the terminal width allows for long
+    /// lists.
+    fn write_columns(&mut self, options: &[(String, bool)]) -> io::Result<()> {
+        let cell = options
+            .iter()
+            .map(|(option, _)| option.chars().count())
+            .max();
+        let columns = match cell {
+            Some(cell) if options.len() > MULTI_COLUMN_THRESHOLD && !self.accessible => {
+                (self.width + COLUMN_GAP) / (cell + COLUMN_GAP)
+            }
+            _ => 1,
+        };
+
+        let rows = options.len().div_ceil(columns.max(1));
+        let cell = cell.unwrap_or_default() + COLUMN_GAP;
+
+        for row in 0..rows {
+            let cells: Vec<&(String, bool)> = options.iter().skip(row).step_by(rows).collect();
+
+            for (column, (option, disabled)) in cells.iter().enumerate() {
+                let width = if column + 1 < cells.len() { cell } else { 0 };
+                let option = format!("{option:width$}");
+
+                if *disabled && self.decorated() && !self.theme.high_contrast {
+                    write!(self.frame, "\x1b[2m{option}\x1b[0m")?;
+                } else {
+                    write!(self.frame, "{option}")?;
+                }
+            }
+
+            writeln!(self.frame)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the index of the choice designated by `answer`.
+fn find_choice<T>(choices: &[Choice<T>], answer: &str, matching: Matching) -> Option<usize> {
+    match answer.parse::<usize>() {
+        Ok(number) => number.checked_sub(1).filter(|index| *index < choices.len()),
+        Err(_) => choices
+            .iter()
+            .position(|choice| choice.matches(answer, matching)),
+    }
+}
+
+fn checked<T>(choices: &[Choice<T>]) -> Vec<usize> {
+    (0..choices.len())
+        .filter(|index| choices[*index].checked)
+        .collect()
+}
+
+/// Returns the indices of the choices which aren't disabled and match `filter`.
+fn available<T>(choices: &[Choice<T>], filter: impl Fn(&Choice<T>) -> bool) -> Vec<usize> {
+    (0..choices.len())
+        .filter(|index| choices[*index].disabled.is_none() && filter(&choices[*index]))
+        .collect()
+}
+
+/// Parses the indices of the choices listed in `answer`.
+fn parse_selection<T>(
+    choices: &[Choice<T>],
+    answer: &str,
+    matching: Matching,
+    messages: &Messages,
+) -> Result<Vec<usize>, String> {
+    let mut indices = Vec::new();
+
+    for token in answer
+        .split(',')
+        .map(str::trim)
+        .filter(|token| !token.is_empty())
+    {
+        let range = find_choice(choices, token, matching)
+            .map(|index| index..=index)
+            .or_else(|| parse_range(choices.len(), token))
+            .ok_or_else(|| messages.format("multi-select-unknown", &[("token", &token)]))?;
+
+        for index in range {
+            if let Some(message) = choices[index].unavailable(messages) {
+                return Err(message);
+            }
+
+            indices.push(index);
+        }
+    }
+
+    indices.sort_unstable();
+    indices.dedup();
+
+    Ok(indices)
+}
+
+/// Parses a range of choice numbers such as `5-7` into their indices, if
+/// within the `count` choices.
+fn parse_range(count: usize, token: &str) -> Option<RangeInclusive<usize>> {
+    let (start, end) = token.split_once('-')?;
+    let start: usize = start.parse().ok()?;
+    let end: usize = end.parse().ok()?;
+
+    (1 <= start && start <= end && end <= count).then(|| start - 1..=end - 1)
+}
+
+fn check_count(
+    indices: Vec<usize>,
+    count: &impl RangeBounds<usize>,
+    messages: &Messages,
+) -> Result<Vec<usize>, String> {
+    if count.contains(&indices.len()) {
+        return Ok(indices);
+    }
+
+    let (min, max) = crate::inclusive_bounds(count);
+
+    let id = match max {
+        Some(max) if min == max => "count-exactly",
+        Some(_) if min > 0 => "count-between",
+        Some(_) => "count-at-most",
+        None => "count-at-least",
+    };
+    let max = max.unwrap_or_default();
+
+    Err(messages.format(id, &[("min", &min), ("max", &max)]))
+}
+
+fn take_selections<T>(choices: Vec<Choice<T>>, indices: &[usize]) -> Vec<Selection<T>> {
+    choices
+        .into_iter()
+        .enumerate()
+        .filter(|(index, _)| indices.contains(index))
+        .map(|(index, choice)| Selection {
+            index,
+            value: choice.value,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tests::CountingWriter, Theme};
+
+    const ENVIRONMENT: &str = "Environment?";
+
+    fn select(input: &str) -> (Option<Selection<String>>, String) {
+        let mut output = Vec::new();
+        let result = Question::new(input.as_bytes(), &mut output)
+            .select(
+                ENVIRONMENT,
+                [Choice::new("production").alias("p"), Choice::new("staging")],
+            )
+            .expect("select() should not fail");
+
+        (
+            result,
+            String::from_utf8(output).expect("from_utf8() should not fail"),
+        )
+    }
+
+    #[test]
+    fn select_should_display_a_numbered_menu() {
+        let (_, output) = select("1\n");
+
+        assert_eq!(
+            output,
+            "Environment?\n  1) production [p]\n  2) staging\nChoose 1-2: "
+        );
+    }
+
+    #[test]
+    fn select_should_replace_the_previous_attempt_in_ansi_mode() {
+        let mut output = Vec::new();
+
+        Question::new("9\n9\n1\n".as_bytes(), &mut output)
+            .ansi(true)
+            .echo_answers(false)
+            .select("Environment?", ["production", "staging"])
+            .expect("select() should not fail");
+        let output = String::from_utf8(output).expect("from_utf8() should not fail");
+
+        assert!(output.ends_with(
+            "Choose 1-2: \x1b[1A\r\x1b[JPlease choose a number between 1 and 2.\n\
+             Choose 1-2: \x1b[2A\r\x1b[JPlease choose a number between 1 and 2.\n\
+             Choose 1-2: "
+        ));
+    }
+
+    #[test]
+    fn multi_select_should_replace_the_previous_attempt_in_ansi_mode() {
+        let mut output = Vec::new();
+
+        Question::new("9\n9\n1\n".as_bytes(), &mut output)
+            .ansi(true)
+            .echo_answers(false)
+            .multi_select("Features?", ["logs", "metrics"])
+            .expect("multi_select() should not fail");
+        let output = String::from_utf8(output).expect("from_utf8() should not fail");
+
+        assert!(output.contains("\x1b[1A\r\x1b[J'9' is not one of the choices.\n"));
+        assert!(output.contains("\x1b[2A\r\x1b[J'9' is not one of the choices.\n"));
+    }
+
+    #[test]
+    fn select_should_lay_out_a_long_list_of_short_labels_in_columns() {
+        let mut output = Vec::new();
+
+        Question::new("1\n".as_bytes(), &mut output)
+            .width(40)
+            .select("Letter?", ('a'..='k').map(String::from))
+            .expect("select() should not fail");
+        let output = String::from_utf8(output).expect("from_utf8() should not fail");
+
+        assert_eq!(
+            output,
+            "Letter?\n\
+             \x20\x20 1) a     4) d     7) g    10) j\n\
+             \x20\x20 2) b     5) e     8) h    11) k\n\
+             \x20\x20 3) c     6) f     9) i\n\
+             Choose 1-11: "
+        );
+    }
+
+    #[test]
+    fn select_should_list_the_choices_one_per_line_when_accessible() {
+        let mut output = Vec::new();
+
+        Question::new("1\n".as_bytes(), &mut output)
+            .ansi(true)
+            .accessible(true)
+            .select(
+                "Letter?",
+                ('a'..='k').map(|letter| Choice::new(letter).disabled("taken")),
+            )
+            .expect("select() should not fail");
+        let output = String::from_utf8(output).expect("from_utf8() should not fail");
+
+        assert!(output.starts_with("Letter?\n   1) a (taken)\n   2) b (taken)\n"));
+        assert!(!output.contains('\x1b'));
+    }
+
+    #[test]
+    fn select_should_display_the_group_headers() {
+        let mut output = Vec::new();
+        let result = Question::new("3\n".as_bytes(), &mut output)
+            .select(
+                "Service?",
+                [
+                    Choice::new("postgres").group("Databases"),
+                    Choice::new("mysql").group("Databases"),
+                    Choice::new("redis").group("Caches"),
+                ],
+            )
+            .expect("select() should not fail");
+        let output = String::from_utf8(output).expect("from_utf8() should not fail");
+
+        assert_eq!(
+            result.map(|selection| selection.value),
+            Some(String::from("redis"))
+        );
+        assert_eq!(
+            output,
+            "Service?\nDatabases\n  1) postgres\n  2) mysql\nCaches\n  3) redis\nChoose 1-3: "
+        );
+    }
+
+    #[test]
+    fn select_should_display_the_descriptions_under_the_labels() {
+        let mut output = Vec::new();
+
+        Question::new("1\n".as_bytes(), &mut output)
+            .select(
+                ENVIRONMENT,
+                [
+                    Choice::new("production").description("Serves the customers"),
+                    Choice::new("staging"),
+                ],
+            )
+            .expect("select() should not fail");
+        let output = String::from_utf8(output).expect("from_utf8() should not fail");
+
+        assert!(output.starts_with(
+            "Environment?\n  1) production\n     Serves the customers\n  2) staging\n"
+        ));
+    }
+
+    #[test]
+    fn select_should_dim_the_disabled_choices_and_refuse_them() {
+        let mut output = Vec::new();
+        let result = Question::new("2\n1\n".as_bytes(), &mut output)
+            .ansi(true)
+            .select(
+                ENVIRONMENT,
+                [
+                    Choice::new("production"),
+                    Choice::new("staging").disabled("requires --experimental"),
+                ],
+            )
+            .expect("select() should not fail");
+        let output = String::from_utf8(output).expect("from_utf8() should not fail");
+
+        assert_eq!(result.map(|selection| selection.index), Some(0));
+        assert!(output.starts_with(
+            "Environment?\n  1) production\n\x1b[2m  2) staging (requires --experimental)\x1b[0m\n"
+        ));
+        assert!(output.contains("'staging' is unavailable: requires --experimental.\n"));
+    }
+
+    #[test]
+    fn select_should_not_dim_anything_with_a_high_contrast_theme() {
+        let mut output = Vec::new();
+
+        Question::new("1\n".as_bytes(), &mut output)
+            .ansi(true)
+            .echo_answers(false)
+            .theme(Theme::high_contrast())
+            .select(
+                ENVIRONMENT,
+                [
+                    Choice::new("production").group("Live"),
+                    Choice::new("staging").group("Live").disabled("frozen"),
+                ],
+            )
+            .expect("select() should not fail");
+        let output = String::from_utf8(output).expect("from_utf8() should not fail");
+
+        assert_eq!(
+            output,
+            "Environment?\n\x1b[1mLive\x1b[0m\n  1) production\n  2) staging (frozen)\n\
+             Choose 1-2: "
+        );
+    }
+
+    #[test]
+    fn select_should_write_the_whole_menu_at_once() {
+        let mut output = CountingWriter::default();
+
+        Question::new("1\n".as_bytes(), &mut output)
+            .select(ENVIRONMENT, ["production", "staging"])
+            .expect("select() should not fail");
+
+        assert_eq!(output.writes, 1);
+    }
+
+    #[test]
+    fn select_should_return_the_choice_matching_the_number() {
+        let (result, _) = select("2\n");
+
+        assert_eq!(
+            result,
+            Some(Selection {
+                index: 1,
+                value: String::from("staging")
+            })
+        );
+    }
+
+    #[test]
+    fn select_should_return_the_choice_matching_an_alias() {
+        let (result, _) = select("p\n");
+
+        assert_eq!(result.map(|selection| selection.index), Some(0));
+    }
+
+    #[test]
+    fn select_should_ask_again_for_an_out_of_range_number() {
+        let (result, output) = select("3\n0\n1\n");
+
+        assert_eq!(result.map(|selection| selection.index), Some(0));
+        assert!(output.ends_with(
+            "Please choose a number between 1 and 2.\nChoose 1-2: \
+             Please choose a number between 1 and 2.\nChoose 1-2: "
+        ));
+    }
+
+    #[test]
+    fn select_should_return_none_for_eof() {
+        let (result, _) = select("");
+
+        assert!(result.is_none());
+    }
+
+    fn multi_select(input: &str) -> (Option<Vec<usize>>, String) {
+        let mut output = Vec::new();
+        let result = Question::new(input.as_bytes(), &mut output)
+            .multi_select(
+                "Features?",
+                [
+                    Choice::new("logs").checked(true),
+                    Choice::new("metrics").alias("m"),
+                    Choice::new("traces").checked(true),
+                ],
+            )
+            .expect("multi_select() should not fail");
+
+        (
+            result.map(|selections| selections.iter().map(|selection| selection.index).collect()),
+            String::from_utf8(output).expect("from_utf8() should not fail"),
+        )
+    }
+
+    #[test]
+    fn multi_select_should_display_the_checked_choices() {
+        let (_, output) = multi_select("\n");
+
+        assert_eq!(
+            output,
+            "Features?\n  1) [x] logs\n  2) [ ] metrics [m]\n  3) [x] traces\n\
+             Choose among 1-3, separated by commas (or all, none, invert): "
+        );
+    }
+
+    #[test]
+    fn multi_select_should_keep_the_checked_choices_for_an_empty_answer() {
+        let (result, _) = multi_select("\n");
+
+        assert_eq!(result, Some(vec![0, 2]));
+    }
+
+    #[test]
+    fn multi_select_should_return_the_listed_choices_in_menu_order() {
+        let (result, output) = multi_select("3, m 3\n3, m,3\n");
+
+        assert_eq!(result, Some(vec![1, 2]));
+        assert!(output.contains("'m 3' is not one of the choices.\n"));
+    }
+
+    #[test]
+    fn multi_select_should_select_all_the_choices() {
+        let (result, _) = multi_select("all\n");
+
+        assert_eq!(result, Some(vec![0, 1, 2]));
+    }
+
+    #[test]
+    fn multi_select_should_select_none_of_the_choices() {
+        let (result, _) = multi_select("none\n");
+
+        assert_eq!(result, Some(vec![]));
+    }
+
+    #[test]
+    fn multi_select_should_invert_the_checked_choices() {
+        let (result, _) = multi_select("invert\n");
+
+        assert_eq!(result, Some(vec![1]));
+    }
+
+    #[test]
+    fn multi_select_should_ask_again_for_an_unknown_choice() {
+        let (result, output) = multi_select("1,4\n1\n");
+
+        assert_eq!(result, Some(vec![0]));
+        assert!(output.contains("'4' is not one of the choices.\n"));
+    }
+
+    #[test]
+    fn multi_select_should_select_the_ranges_of_choices() {
+        let (result, output) = multi_select("2-4\n2-3\n");
+
+        assert_eq!(result, Some(vec![1, 2]));
+        assert!(output.contains("'2-4' is not one of the choices.\n"));
+    }
+
+    fn multi_select_within(input: &str, count: impl RangeBounds<usize>) -> (Option<usize>, String) {
+        let mut output = Vec::new();
+        let result = Question::new(input.as_bytes(), &mut output)
+            .multi_select_within("Features?", ["logs", "metrics", "traces"], count)
+            .expect("multi_select_within() should not fail");
+
+        (
+            result.map(|selections| selections.len()),
+            String::from_utf8(output).expect("from_utf8() should not fail"),
+        )
+    }
+
+    #[test]
+    fn multi_select_within_should_ask_again_until_enough_choices_are_selected() {
+        let (result, output) = multi_select_within("\n1\n1,2\n", 2..);
+
+        assert_eq!(result, Some(2));
+        assert_eq!(output.matches("Please choose at least 2.\n").count(), 2);
+    }
+
+    #[test]
+    fn multi_select_within_should_ask_again_while_too_many_choices_are_selected() {
+        let (result, output) = multi_select_within("1,2,3\n1\n", 1..=2);
+
+        assert_eq!(result, Some(1));
+        assert!(output.contains("Please choose between 1 and 2.\n"));
+    }
+}
@@ -0,0 +1,80 @@
+use crate::Question;
+use std::{
+    io,
+    sync::{Arc, Mutex, PoisonError},
+};
+
+/// A [`Question`] shared between threads, which asks one question at a time so
+/// that the questions of different threads never interleave.
+pub struct SharedQuestion<R, W> {
+    inner: Arc<Mutex<Question<R, W>>>,
+}
+
+impl<R, W> Clone for SharedQuestion<R, W> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl<R: io::BufRead, W: io::Write> SharedQuestion<R, W> {
+    pub fn new(question: Question<R, W>) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(question)),
+        }
+    }
+
+    pub fn ask(&self, question: impl Into<String>) -> io::Result<Option<String>> {
+        self.with(|inner| inner.ask(question))
+    }
+
+    /// Gives `f` exclusive access to the question, e.g. to ask several
+    /// questions in a row without another thread asking in between.
+    pub fn with<T>(&self, f: impl FnOnce(&mut Question<R, W>) -> T) -> T {
+        let mut inner = self.inner.lock().unwrap_or_else(PoisonError::into_inner);
+
+        f(&mut inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn shared_question_should_be_usable_from_several_threads() {
+        let shared = SharedQuestion::new(Question::new("a\nb\n".as_bytes(), io::sink()));
+        let workers: Vec<_> = (0..2)
+            .map(|_| {
+                let shared = shared.clone();
+
+                thread::spawn(move || shared.ask("Letter?").expect("ask() should not fail"))
+            })
+            .collect();
+        let mut answers: Vec<_> = workers
+            .into_iter()
+            .map(|worker| worker.join().expect("join() should not fail"))
+            .collect();
+
+        answers.sort();
+        assert_eq!(answers, [Some(String::from("a")), Some(String::from("b"))]);
+    }
+
+    #[test]
+    fn with_should_ask_several_questions_in_a_row() {
+        let mut output = Vec::new();
+        let shared = SharedQuestion::new(Question::new("a\nb\n".as_bytes(), &mut output));
+        let answers = shared.with(|question| {
+            let first = question.ask("First?").expect("ask() should not fail");
+            let second = question.ask("Second?").expect("ask() should not fail");
+
+            (first, second)
+        });
+
+        drop(shared);
+        assert_eq!(answers, (Some(String::from("a")), Some(String::from("b"))));
+        assert_eq!(output, b"First? Second? ");
+    }
+}
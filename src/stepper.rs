@@ -0,0 +1,122 @@
+use crate::Question;
+use std::{io, ops::RangeInclusive};
+
+impl<R: io::BufRead, W: io::Write> Question<R, W> {
+    /// Asks for a number within `range`, starting at `initial`, which each `+`
+    /// or `-` of the answer (e.g. `++`) increments or decrements by `step`
+    /// before asking again; a number is accepted as well, and the empty answer
+    /// returns the current number.
+    ///
+    /// Fails with an error of kind [`io::ErrorKind::InvalidInput`] when
+    /// `range` is empty, i.e. its start is greater than its end.
+    pub fn ask_stepper(
+        &mut self,
+        question: impl Into<String>,
+        initial: i64,
+        range: RangeInclusive<i64>,
+        step: i64,
+    ) -> io::Result<Option<i64>> {
+        let question = question.into();
+        let (min, max) = (*range.start(), *range.end());
+
+        if min > max {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("the range {min}..={max} is empty"),
+            ));
+        }
+
+        let mut value = initial.clamp(min, max);
+
+        self.validating(|this| loop {
+            let hint = this.messages.format(
+                "stepper-hint",
+                &[
+                    ("value", &value),
+                    ("step", &step),
+                    ("min", &min),
+                    ("max", &max),
+                ],
+            );
+            let Some(answer) = this.ask(format!("{question} {hint}"))? else {
+                return Ok(None);
+            };
+
+            if answer.is_empty() {
+                return Ok(Some(value));
+            }
+
+            let steps = answer.len() as i64;
+
+            if answer.chars().all(|c| c == '+') {
+                value = value.saturating_add(step.saturating_mul(steps)).min(max);
+            } else if answer.chars().all(|c| c == '-') {
+                value = value.saturating_sub(step.saturating_mul(steps)).max(min);
+            } else {
+                match answer.parse() {
+                    Ok(number) if range.contains(&number) => return Ok(Some(number)),
+                    _ => {
+                        let message = this
+                            .messages
+                            .format("stepper-invalid", &[("min", &min), ("max", &max)]);
+
+                        this.write_error(&message)?;
+                    }
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ask_stepper(input: &str) -> (Option<i64>, String) {
+        let mut output = Vec::new();
+        let result = Question::new(input.as_bytes(), &mut output)
+            .ask_stepper("Volume?", 5, 0..=10, 2)
+            .expect("ask_stepper() should not fail");
+
+        (
+            result,
+            String::from_utf8(output).expect("from_utf8() should not fail"),
+        )
+    }
+
+    #[test]
+    fn ask_stepper_should_step_the_number_until_submitted() {
+        let (result, output) = ask_stepper("++\n-\n\n");
+
+        assert_eq!(result, Some(7));
+        assert_eq!(
+            output,
+            "Volume? [5] (+/- by 2, 0-10) Volume? [9] (+/- by 2, 0-10) \
+             Volume? [7] (+/- by 2, 0-10) "
+        );
+    }
+
+    #[test]
+    fn ask_stepper_should_stay_within_the_range() {
+        let (result, _) = ask_stepper("++++\n\n");
+
+        assert_eq!(result, Some(10));
+    }
+
+    #[test]
+    fn ask_stepper_should_accept_a_number_within_the_range() {
+        let (result, output) = ask_stepper("42\n3\n");
+
+        assert_eq!(result, Some(3));
+        assert!(output.contains("Please answer +, - or a number between 0 and 10.\n"));
+    }
+
+    #[test]
+    fn ask_stepper_should_refuse_an_empty_range() {
+        let error = Question::new("\n".as_bytes(), io::sink())
+            .ask_stepper("Volume?", 5, RangeInclusive::new(10, 0), 2)
+            .expect_err("ask_stepper() should fail");
+
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    }
+}
@@ -0,0 +1,197 @@
+use std::env;
+
+/// How the terminal signals an invalid answer, on terminals accepting ANSI
+/// escape sequences only.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Bell {
+    #[default]
+    Off,
+    /// Rings the terminal bell.
+    Audible,
+    /// Briefly flashes the screen by reversing its colors.
+    Visual,
+}
+
+/// How many colors the terminal can display.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColorDepth {
+    /// The 16 colors of the base palette, which any terminal displays.
+    #[default]
+    Ansi16,
+    Ansi256,
+    TrueColor,
+}
+
+impl ColorDepth {
+    /// Detects the color depth from `COLORTERM`, set to `truecolor` or `24bit`
+    /// by the terminals displaying any color, then from `TERM`, e.g.
+    /// `xterm-256color`.
+    pub fn from_env() -> Self {
+        let colorterm = env::var("COLORTERM").unwrap_or_default();
+        let term = env::var("TERM").unwrap_or_default();
+
+        if colorterm == "truecolor" || colorterm == "24bit" {
+            Self::TrueColor
+        } else if term.contains("256color") {
+            Self::Ansi256
+        } else {
+            Self::Ansi16
+        }
+    }
+}
+
+/// A color of a theme, rendered as close as the color depth allows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+/// The colors of the base palette, as rendered by xterm.
+const PALETTE: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+impl Color {
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    /// Returns the escape sequence setting the foreground to this color.
+    pub(crate) fn foreground(self, depth: ColorDepth) -> String {
+        let Self { r, g, b } = self;
+
+        match depth {
+            ColorDepth::TrueColor => format!("\x1b[38;2;{r};{g};{b}m"),
+            ColorDepth::Ansi256 => {
+                let level = |value: u8| (u16::from(value) * 5 + 127) / 255;
+
+                format!(
+                    "\x1b[38;5;{}m",
+                    16 + 36 * level(r) + 6 * level(g) + level(b)
+                )
+            }
+            ColorDepth::Ansi16 => {
+                let distance = |(pr, pg, pb): (u8, u8, u8)| {
+                    [(r, pr), (g, pg), (b, pb)]
+                        .iter()
+                        .map(|(a, b)| u32::from(a.abs_diff(*b)).pow(2))
+                        .sum::<u32>()
+                };
+                let index = (0..PALETTE.len())
+                    .min_by_key(|index| distance(PALETTE[*index]))
+                    .unwrap_or_default();
+
+                match index {
+                    0..=7 => format!("\x1b[{}m", 30 + index),
+                    _ => format!("\x1b[{}m", 90 + index - 8),
+                }
+            }
+        }
+    }
+}
+
+/// The kind of input a question expects.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) enum Prompt {
+    #[default]
+    Question,
+    Confirm,
+    Secret,
+    #[cfg(feature = "select")]
+    Select,
+}
+
+/// The markers leading the questions by the kind of input they expect, none
+/// by default.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Icons {
+    pub question: String,
+    pub confirm: String,
+    pub secret: String,
+    pub select: String,
+}
+
+impl Icons {
+    /// Returns the `?`, `✓`, `•` and `≡` markers.
+    pub fn symbols() -> Self {
+        Self {
+            question: String::from("?"),
+            confirm: String::from("✓"),
+            secret: String::from("•"),
+            select: String::from("≡"),
+        }
+    }
+
+    pub(crate) fn get(&self, prompt: Prompt) -> &str {
+        match prompt {
+            Prompt::Question => &self.question,
+            Prompt::Confirm => &self.confirm,
+            Prompt::Secret => &self.secret,
+            #[cfg(feature = "select")]
+            Prompt::Select => &self.select,
+        }
+    }
+}
+
+/// The look and feel of the questions.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Theme {
+    pub bell: Bell,
+    /// Colors the questions, on terminals accepting ANSI escape sequences.
+    pub accent: Option<Color>,
+    /// Colors the messages refusing an answer, on terminals accepting ANSI
+    /// escape sequences; when accessible, where colors go unnoticed, the
+    /// messages are led by the `error-label` message instead.
+    pub error: Option<Color>,
+    /// Leads the questions, followed by a space.
+    pub icons: Icons,
+    /// Never dims text, relying on explicit markers such as the reason why a
+    /// choice is disabled, and highlights the group headers in bold, for
+    /// low-vision users, as well as the messages refusing an answer.
+    pub high_contrast: bool,
+}
+
+impl Theme {
+    /// Returns the theme for low-vision users, refusing answers in bright red.
+    pub fn high_contrast() -> Self {
+        Self {
+            error: Some(Color::rgb(255, 0, 0)),
+            high_contrast: true,
+            ..Self::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn foreground_should_render_the_closest_color_of_the_depth() {
+        let orange = Color::rgb(255, 135, 0);
+
+        assert_eq!(
+            orange.foreground(ColorDepth::TrueColor),
+            "\x1b[38;2;255;135;0m"
+        );
+        assert_eq!(orange.foreground(ColorDepth::Ansi256), "\x1b[38;5;214m");
+        assert_eq!(orange.foreground(ColorDepth::Ansi16), "\x1b[33m");
+    }
+}
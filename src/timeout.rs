@@ -0,0 +1,611 @@
+use crate::{matching::Matching, Question, QuestionError};
+use std::{
+    io::{self, BufRead, Write},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc,
+    },
+    thread,
+    time::Duration,
+};
+
+const TICK: Duration = Duration::from_secs(1);
+const POLL: Duration = Duration::from_millis(50);
+
+/// A reader able to wait for a line during a limited amount of time.
+pub trait TimeoutRead: BufRead {
+    /// Reads a line like [`BufRead::read_line`], or returns `Ok(None)` if no
+    /// line was available before the timeout elapsed, leaving in `buf` the
+    /// beginning of the line read so far, if any.
+    fn read_line_timeout(
+        &mut self,
+        buf: &mut String,
+        timeout: Duration,
+    ) -> io::Result<Option<usize>>;
+}
+
+impl<T: TimeoutRead + ?Sized> TimeoutRead for &mut T {
+    fn read_line_timeout(
+        &mut self,
+        buf: &mut String,
+        timeout: Duration,
+    ) -> io::Result<Option<usize>> {
+        (**self).read_line_timeout(buf, timeout)
+    }
+}
+
+impl<T: TimeoutRead + ?Sized> TimeoutRead for Box<T> {
+    fn read_line_timeout(
+        &mut self,
+        buf: &mut String,
+        timeout: Duration,
+    ) -> io::Result<Option<usize>> {
+        (**self).read_line_timeout(buf, timeout)
+    }
+}
+
+impl TimeoutRead for &[u8] {
+    fn read_line_timeout(&mut self, buf: &mut String, _: Duration) -> io::Result<Option<usize>> {
+        self.read_line(buf).map(Some)
+    }
+}
+
+impl TimeoutRead for io::Empty {
+    fn read_line_timeout(&mut self, buf: &mut String, _: Duration) -> io::Result<Option<usize>> {
+        self.read_line(buf).map(Some)
+    }
+}
+
+/// Reads lines from a source on a background thread so that they can be
+/// awaited with a timeout.
+pub struct ThreadedReader {
+    receiver: mpsc::Receiver<io::Result<Vec<u8>>>,
+    buffer: Vec<u8>,
+    position: usize,
+}
+
+impl ThreadedReader {
+    pub fn new<R: io::Read + Send + 'static>(source: R) -> Self {
+        let (sender, receiver) = mpsc::channel();
+
+        thread::spawn(move || {
+            let mut source = io::BufReader::new(source);
+
+            loop {
+                let mut line = Vec::new();
+
+                match source.read_until(b'\n', &mut line) {
+                    Ok(0) => break,
+                    Ok(_) => {
+                        if sender.send(Ok(line)).is_err() {
+                            break;
+                        }
+                    }
+                    Err(error) => {
+                        let _ = sender.send(Err(error));
+                        break;
+                    }
+                }
+            }
+        });
+
+        Self {
+            receiver,
+            buffer: Vec::new(),
+            position: 0,
+        }
+    }
+
+    pub fn stdin() -> Self {
+        Self::new(io::stdin())
+    }
+
+    fn store(&mut self, received: io::Result<Vec<u8>>) -> io::Result<()> {
+        self.buffer = received?;
+        self.position = 0;
+
+        Ok(())
+    }
+}
+
+impl io::Read for ThreadedReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let n = available.len().min(buf.len());
+
+        buf[..n].copy_from_slice(&available[..n]);
+        self.consume(n);
+
+        Ok(n)
+    }
+}
+
+impl BufRead for ThreadedReader {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.position == self.buffer.len() {
+            match self.receiver.recv() {
+                Ok(received) => self.store(received)?,
+                Err(mpsc::RecvError) => {
+                    self.buffer.clear();
+                    self.position = 0;
+                }
+            }
+        }
+
+        Ok(&self.buffer[self.position..])
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.position = (self.position + amount).min(self.buffer.len());
+    }
+}
+
+impl TimeoutRead for ThreadedReader {
+    fn read_line_timeout(
+        &mut self,
+        buf: &mut String,
+        timeout: Duration,
+    ) -> io::Result<Option<usize>> {
+        if self.position == self.buffer.len() {
+            match self.receiver.recv_timeout(timeout) {
+                Ok(received) => self.store(received)?,
+                Err(mpsc::RecvTimeoutError::Timeout) => return Ok(None),
+                Err(mpsc::RecvTimeoutError::Disconnected) => return Ok(Some(0)),
+            }
+        }
+
+        self.read_line(buf).map(Some)
+    }
+}
+
+impl<R: TimeoutRead, W: io::Write> Question<R, W> {
+    /// Asks a question and fails with [`io::ErrorKind::TimedOut`] if no answer
+    /// was given in time.
+    pub fn ask_timeout(
+        &mut self,
+        question: impl Into<String>,
+        timeout: Duration,
+    ) -> io::Result<Option<String>> {
+        self.write_question(question)?;
+
+        let mut buffer = String::new();
+
+        match self.reader.read_line_timeout(&mut buffer, timeout)? {
+            None => Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                "no answer was given in time",
+            )),
+            Some(n) => self.answer(n, buffer),
+        }
+    }
+
+    /// Asks a question and submits `default` if no answer was given in time,
+    /// rendering a countdown after the question while waiting; the default is
+    /// then echoed and recorded like an answer.
+    ///
+    /// The countdown is redrawn in place on terminals accepting ANSI escape
+    /// sequences only, leaving the cursor where the answer is typed, and is
+    /// rendered once otherwise. Once some of the answer was read, it stops,
+    /// waiting for the rest of the answer.
+    pub fn ask_timeout_or(
+        &mut self,
+        question: impl Into<String>,
+        timeout: Duration,
+        default: impl Into<String>,
+    ) -> io::Result<Option<String>> {
+        let default = default.into();
+        let mut remaining = timeout;
+        let mut rendered: usize = 0;
+        let mut buffer = String::new();
+
+        self.write_question(question)?;
+
+        let column = (self.last_line_width() + 1) % self.width.max(1) + 1;
+
+        loop {
+            let countdown = self.messages.format(
+                "timeout-countdown",
+                &[("default", &default), ("seconds", &ceil_secs(remaining))],
+            );
+            let countdown = format!("{countdown} ");
+            let width = countdown.chars().count();
+
+            if rendered == 0 {
+                self.frame.extend_from_slice(countdown.as_bytes());
+                rendered = width;
+            } else if self.decorated() {
+                let padding = rendered.saturating_sub(width);
+
+                write!(
+                    self.frame,
+                    "\x1b7\x1b[{column}G{countdown}{}\x1b8",
+                    " ".repeat(padding)
+                )?;
+                rendered = rendered.max(width);
+            }
+
+            self.render()?;
+
+            let wait = remaining.min(TICK);
+
+            match self.reader.read_line_timeout(&mut buffer, wait)? {
+                None if buffer.is_empty() => {}
+                None => {
+                    let n = buffer.len() + self.reader.read_line(&mut buffer)?;
+
+                    return self.answer(n, buffer);
+                }
+                Some(n) => return self.answer(n, buffer),
+            }
+
+            remaining = remaining.saturating_sub(wait);
+
+            if remaining.is_zero() {
+                let submitted = format!("{default}\n");
+
+                self.frame.extend_from_slice(submitted.as_bytes());
+                self.render()?;
+
+                return self.answer(submitted.len(), submitted);
+            }
+        }
+    }
+
+    /// Asks a yes or no question like [`Question::confirm`], rendering a
+    /// countdown while waiting and submitting `default` once `seconds` have
+    /// elapsed without an answer, e.g. before rebooting.
+    pub fn confirm_with_countdown(
+        &mut self,
+        question: impl Into<String>,
+        default: bool,
+        seconds: u64,
+    ) -> io::Result<Option<bool>> {
+        let hint = match default {
+            true => "confirm-hint-yes",
+            false => "confirm-hint-no",
+        };
+        let question = format!("{} {}", question.into(), self.messages.format(hint, &[]));
+        let yes = self.messages.list("confirm-yes");
+        let no = self.messages.list("confirm-no");
+        let keyword = match default {
+            true => yes.last(),
+            false => no.last(),
+        }
+        .cloned()
+        .unwrap_or_default();
+        let matching = Matching {
+            ignore_case: true,
+            ..self.matching
+        };
+
+        self.validating(|this| loop {
+            let timeout = Duration::from_secs(seconds);
+            let Some(answer) = this.ask_timeout_or(question.as_str(), timeout, keyword.as_str())?
+            else {
+                return Ok(None);
+            };
+
+            if answer.is_empty() {
+                return Ok(Some(default));
+            } else if yes.iter().any(|yes| matching.eq(&answer, yes)) {
+                return Ok(Some(true));
+            } else if no.iter().any(|no| matching.eq(&answer, no)) {
+                return Ok(Some(false));
+            }
+
+            let message = this.messages.format("confirm-invalid", &[]);
+
+            this.write_error(&message)?;
+        })
+    }
+
+    /// Asks a question and, if no answer was given after `idle`, writes
+    /// `reminder` on a line of its own and asks the question again, still
+    /// reading the same answer; what was typed so far isn't lost.
+    pub fn ask_with_reminder(
+        &mut self,
+        question: impl Into<String>,
+        idle: Duration,
+        reminder: impl Into<String>,
+    ) -> io::Result<Option<String>> {
+        let question = crate::ensure_ends_with_whitespace(question);
+
+        self.write_question(question.as_str())?;
+
+        let mut buffer = String::new();
+        let n = match self.reader.read_line_timeout(&mut buffer, idle)? {
+            Some(n) => n,
+            None => {
+                write!(self.frame, "\n{}\n{question}", reminder.into())?;
+                self.render()?;
+                self.reader.read_line(&mut buffer)?
+            }
+        };
+
+        self.answer(n, buffer)
+    }
+
+    /// Asks a question and gives up with [`QuestionError::Cancelled`] as soon
+    /// as `cancelled` is set, e.g. from another thread.
+    pub fn ask_cancellable(
+        &mut self,
+        question: impl Into<String>,
+        cancelled: &AtomicBool,
+    ) -> Result<Option<String>, QuestionError> {
+        self.write_question(question)?;
+
+        let mut buffer = String::new();
+
+        loop {
+            if cancelled.load(Ordering::Relaxed) {
+                self.frame.push(b'\n');
+                self.render()?;
+                self.observer.cancelled();
+
+                return Err(QuestionError::Cancelled);
+            }
+
+            match self.reader.read_line_timeout(&mut buffer, POLL)? {
+                None => {}
+                Some(n) => return Ok(self.answer(n, buffer)?),
+            }
+        }
+    }
+}
+
+fn ceil_secs(duration: Duration) -> u64 {
+    duration.as_secs() + u64::from(duration.subsec_nanos() > 0)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    const CONTINUE: &str = "Continue?";
+
+    /// A reader on which no answer ever arrives.
+    pub(crate) struct Silent;
+
+    impl io::Read for Silent {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Ok(0)
+        }
+    }
+
+    impl BufRead for Silent {
+        fn fill_buf(&mut self) -> io::Result<&[u8]> {
+            Ok(&[])
+        }
+
+        fn consume(&mut self, _: usize) {}
+    }
+
+    impl TimeoutRead for Silent {
+        fn read_line_timeout(&mut self, _: &mut String, _: Duration) -> io::Result<Option<usize>> {
+            Ok(None)
+        }
+    }
+
+    #[test]
+    fn ask_with_reminder_should_remind_the_question_once_idle() {
+        let mut output = Vec::new();
+        let result = Question::new(Silent, &mut output)
+            .ask_with_reminder(CONTINUE, TICK, "Still there?")
+            .expect("ask_with_reminder() should not fail");
+
+        assert_eq!(result, None);
+        assert_eq!(output, b"Continue? \nStill there?\nContinue? ");
+    }
+
+    #[test]
+    fn ask_with_reminder_should_not_remind_an_answered_question() {
+        let mut output = Vec::new();
+        let result = Question::new("yes\n".as_bytes(), &mut output)
+            .ask_with_reminder(CONTINUE, TICK, "Still there?")
+            .expect("ask_with_reminder() should not fail");
+
+        assert_eq!(result, Some(String::from("yes")));
+        assert_eq!(output, b"Continue? ");
+    }
+
+    #[test]
+    fn ask_timeout_should_return_the_answer_given_in_time() {
+        let result = Question::new("yes".as_bytes(), io::sink())
+            .ask_timeout(CONTINUE, TICK)
+            .expect("ask_timeout() should not fail");
+
+        assert_eq!(result, Some(String::from("yes")));
+    }
+
+    #[test]
+    fn ask_timeout_should_fail_when_no_answer_is_given_in_time() {
+        let error = Question::new(Silent, io::sink())
+            .ask_timeout(CONTINUE, TICK)
+            .expect_err("ask_timeout() should fail");
+
+        assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+    }
+
+    #[test]
+    fn ask_timeout_or_should_render_the_countdown_once_when_accessible() {
+        let mut output = Vec::new();
+        let result = Question::new(Silent, &mut output)
+            .accessible(true)
+            .ask_timeout_or(CONTINUE, Duration::from_millis(1500), "yes")
+            .expect("ask_timeout_or() should not fail");
+
+        assert_eq!(result, Some(String::from("yes")));
+        assert_eq!(
+            String::from_utf8(output).expect("from_utf8() should not fail"),
+            "Continue? (continuing with 'yes' in 2s…) yes\n"
+        );
+    }
+
+    #[test]
+    fn ask_timeout_or_should_submit_the_default_when_no_answer_is_given_in_time() {
+        let mut output = Vec::new();
+        let result = Question::new(Silent, &mut output)
+            .ask_timeout_or(CONTINUE, Duration::from_secs(2), "yes")
+            .expect("ask_timeout_or() should not fail");
+        let output = String::from_utf8(output).expect("from_utf8() should not fail");
+
+        assert_eq!(result, Some(String::from("yes")));
+        assert_eq!(output, "Continue? (continuing with 'yes' in 2s…) yes\n");
+    }
+
+    #[test]
+    fn ask_timeout_or_should_redraw_the_countdown_in_place_in_ansi_mode() {
+        let mut output = Vec::new();
+
+        Question::new(Silent, &mut output)
+            .ansi(true)
+            .echo_answers(false)
+            .ask_timeout_or(CONTINUE, Duration::from_secs(2), "yes")
+            .expect("ask_timeout_or() should not fail");
+        let output = String::from_utf8(output).expect("from_utf8() should not fail");
+
+        assert_eq!(
+            output,
+            "Continue? (continuing with 'yes' in 2s…) \
+             \x1b7\x1b[11G(continuing with 'yes' in 1s…) \x1b8yes\n"
+        );
+    }
+
+    /// A reader on which an answer is being typed, but never submitted in
+    /// time.
+    struct Typing(&'static [u8]);
+
+    impl io::Read for Typing {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.0.read(buf)
+        }
+    }
+
+    impl BufRead for Typing {
+        fn fill_buf(&mut self) -> io::Result<&[u8]> {
+            self.0.fill_buf()
+        }
+
+        fn consume(&mut self, amount: usize) {
+            self.0.consume(amount);
+        }
+    }
+
+    impl TimeoutRead for Typing {
+        fn read_line_timeout(
+            &mut self,
+            buf: &mut String,
+            _: Duration,
+        ) -> io::Result<Option<usize>> {
+            buf.push('n');
+            Ok(None)
+        }
+    }
+
+    #[test]
+    fn ask_timeout_or_should_stop_the_countdown_once_typing() {
+        let mut output = Vec::new();
+        let result = Question::new(Typing(b"o\n"), &mut output)
+            .ansi(true)
+            .echo_answers(false)
+            .ask_timeout_or(CONTINUE, Duration::from_secs(2), "yes")
+            .expect("ask_timeout_or() should not fail");
+
+        assert_eq!(result, Some(String::from("no")));
+        assert_eq!(
+            output,
+            "Continue? (continuing with 'yes' in 2s…) ".as_bytes()
+        );
+    }
+
+    #[test]
+    fn ask_timeout_or_should_echo_the_submitted_default_in_ansi_mode() {
+        let mut output = Vec::new();
+        let result = Question::new(Silent, &mut output)
+            .ansi(true)
+            .ask_timeout_or(CONTINUE, TICK, "yes")
+            .expect("ask_timeout_or() should not fail");
+        let output = String::from_utf8(output).expect("from_utf8() should not fail");
+
+        assert_eq!(result, Some(String::from("yes")));
+        assert!(output.ends_with("\x1b[32m✔\x1b[39m Continue? … \x1b[36myes\x1b[39m\n"));
+    }
+
+    #[test]
+    fn ask_timeout_or_should_return_the_answer_given_in_time() {
+        let result = Question::new("no".as_bytes(), io::sink())
+            .ask_timeout_or(CONTINUE, TICK, "yes")
+            .expect("ask_timeout_or() should not fail");
+
+        assert_eq!(result, Some(String::from("no")));
+    }
+
+    #[test]
+    fn confirm_with_countdown_should_submit_the_default_in_time() {
+        let mut output = Vec::new();
+        let result = Question::new(Silent, &mut output)
+            .accessible(true)
+            .confirm_with_countdown("Reboot?", true, 1)
+            .expect("confirm_with_countdown() should not fail");
+
+        assert_eq!(result, Some(true));
+        assert_eq!(
+            String::from_utf8(output).expect("from_utf8() should not fail"),
+            "Reboot? [Y/n] (continuing with 'yes' in 1s…) yes\n"
+        );
+    }
+
+    #[test]
+    fn confirm_with_countdown_should_return_the_answer_given_in_time() {
+        let result = Question::new("n\n".as_bytes(), io::sink())
+            .confirm_with_countdown("Reboot?", true, 1)
+            .expect("confirm_with_countdown() should not fail");
+
+        assert_eq!(result, Some(false));
+    }
+
+    #[test]
+    fn ask_cancellable_should_fail_once_cancelled() {
+        let cancelled = AtomicBool::new(true);
+        let error = Question::new(Silent, io::sink())
+            .ask_cancellable(CONTINUE, &cancelled)
+            .expect_err("ask_cancellable() should fail");
+
+        assert!(matches!(error, QuestionError::Cancelled));
+    }
+
+    #[test]
+    fn ask_cancellable_should_return_the_answer_when_not_cancelled() {
+        let cancelled = AtomicBool::new(false);
+        let result = Question::new(ThreadedReader::new("yes\n".as_bytes()), io::sink())
+            .ask_cancellable(CONTINUE, &cancelled)
+            .expect("ask_cancellable() should not fail");
+
+        assert_eq!(result, Some(String::from("yes")));
+    }
+
+    #[test]
+    fn ask_timeout_should_work_through_a_boxed_reader() {
+        let reader: Box<dyn TimeoutRead> = Box::new(Silent);
+        let error = Question::new(reader, io::sink())
+            .ask_timeout(CONTINUE, TICK)
+            .expect_err("ask_timeout() should fail");
+
+        assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+    }
+
+    #[test]
+    fn threaded_reader_should_read_lines_from_its_source() {
+        let mut reader = ThreadedReader::new("first\nsecond\n".as_bytes());
+        let mut buffer = String::new();
+
+        reader
+            .read_line_timeout(&mut buffer, TICK)
+            .expect("read_line_timeout() should not fail");
+        reader
+            .read_line(&mut buffer)
+            .expect("read_line() should not fail");
+
+        assert_eq!(buffer, "first\nsecond\n");
+    }
+}
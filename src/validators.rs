@@ -0,0 +1,292 @@
+//! Validators for [`Question::ask_validated`](crate::Question::ask_validated),
+//! built to be combined, e.g. `length(3..=20).and(alphanumeric())`.
+
+use crate::Messages;
+use std::ops::RangeBounds;
+
+/// A validator of any type, e.g. to [`all`] or [`any`] of different ones.
+pub type BoxedValidator<'a> = Box<dyn Fn(&str) -> Result<(), String> + 'a>;
+
+/// Combines validators, i.e. functions accepting or refusing an answer with
+/// an error message.
+pub trait Validator: Fn(&str) -> Result<(), String> + Sized {
+    /// Accepts the answers accepted by both validators.
+    fn and(
+        self,
+        other: impl Fn(&str) -> Result<(), String>,
+    ) -> impl Fn(&str) -> Result<(), String> {
+        move |answer| self(answer).and_then(|()| other(answer))
+    }
+
+    /// Accepts the answers accepted by either validator, refusing the others
+    /// with the error of the second one.
+    fn or(self, other: impl Fn(&str) -> Result<(), String>) -> impl Fn(&str) -> Result<(), String> {
+        move |answer| self(answer).or_else(|_| other(answer))
+    }
+
+    /// Replaces the error message with the one returned by `f`.
+    fn map_err(self, f: impl Fn(String) -> String) -> impl Fn(&str) -> Result<(), String> {
+        move |answer| self(answer).map_err(&f)
+    }
+}
+
+impl<F: Fn(&str) -> Result<(), String>> Validator for F {}
+
+/// Accepts the answers accepted by every one of `validators`, refusing the
+/// others with the error of the first one refusing them.
+pub fn all<V: Fn(&str) -> Result<(), String>>(
+    validators: impl IntoIterator<Item = V>,
+) -> impl Fn(&str) -> Result<(), String> {
+    let validators: Vec<V> = validators.into_iter().collect();
+
+    move |answer| {
+        validators
+            .iter()
+            .try_for_each(|validator| validator(answer))
+    }
+}
+
+/// Accepts the answers accepted by any of `validators`, refusing the others
+/// with the error of the last one.
+pub fn any<V: Fn(&str) -> Result<(), String>>(
+    validators: impl IntoIterator<Item = V>,
+) -> impl Fn(&str) -> Result<(), String> {
+    let validators: Vec<V> = validators.into_iter().collect();
+
+    move |answer| {
+        let mut result = Ok(());
+
+        for validator in &validators {
+            result = validator(answer);
+
+            if result.is_ok() {
+                break;
+            }
+        }
+
+        result
+    }
+}
+
+/// Accepts the answers refused by `validator`, refusing the others with
+/// `message`.
+pub fn not(
+    validator: impl Fn(&str) -> Result<(), String>,
+    message: impl Into<String>,
+) -> impl Fn(&str) -> Result<(), String> {
+    let message = message.into();
+
+    move |answer| match validator(answer) {
+        Ok(()) => Err(message.clone()),
+        Err(_) => Ok(()),
+    }
+}
+
+/// Accepts the answers whose number of characters is within `range`.
+pub fn length(range: impl RangeBounds<usize>) -> impl Fn(&str) -> Result<(), String> {
+    length_in(range, &Messages::default())
+}
+
+/// Accepts the answers like [`length`], refusing the others with the
+/// `validator-invalid` message of `messages`, e.g. translated.
+pub fn length_in(
+    range: impl RangeBounds<usize>,
+    messages: &Messages,
+) -> impl Fn(&str) -> Result<(), String> {
+    let constraint = length_constraint(&range, messages);
+    let message = messages.format("validator-invalid", &[("constraint", &constraint)]);
+
+    move |answer| {
+        if range.contains(&answer.chars().count()) {
+            Ok(())
+        } else {
+            Err(message.clone())
+        }
+    }
+}
+
+/// Accepts the answers like [`length`], refusing the others with `message`,
+/// where `{value}` stands for the answer and `{constraint}` for the allowed
+/// length, e.g. "between 3 and 20 characters".
+pub fn length_with_message(
+    range: impl RangeBounds<usize>,
+    message: impl Into<String>,
+) -> impl Fn(&str) -> Result<(), String> {
+    let message = message.into();
+    let constraint = length_constraint(&range, &Messages::default());
+
+    move |answer| {
+        if range.contains(&answer.chars().count()) {
+            Ok(())
+        } else {
+            Err(crate::fill_message(&message, answer, &constraint))
+        }
+    }
+}
+
+/// Returns the text of the number of characters allowed by `range`.
+fn length_constraint(range: &impl RangeBounds<usize>, messages: &Messages) -> String {
+    match crate::inclusive_bounds(range) {
+        (min, Some(max)) if min == max => messages.format("length-exactly", &[("min", &min)]),
+        (0, Some(max)) => messages.format("length-at-most", &[("max", &max)]),
+        (min, Some(max)) => messages.format("length-between", &[("min", &min), ("max", &max)]),
+        (min, None) => messages.format("length-at-least", &[("min", &min)]),
+    }
+}
+
+/// Accepts the answers made of letters and digits only.
+pub fn alphanumeric() -> impl Fn(&str) -> Result<(), String> {
+    alphanumeric_in(&Messages::default())
+}
+
+/// Accepts the answers like [`alphanumeric`], refusing the others with the
+/// `validator-invalid` message of `messages`, e.g. translated.
+pub fn alphanumeric_in(messages: &Messages) -> impl Fn(&str) -> Result<(), String> {
+    let constraint = messages.format("alphanumeric", &[]);
+    let message = messages.format("validator-invalid", &[("constraint", &constraint)]);
+
+    move |answer| {
+        if answer.chars().all(char::is_alphanumeric) {
+            Ok(())
+        } else {
+            Err(message.clone())
+        }
+    }
+}
+
+/// Accepts the answers like [`alphanumeric`], refusing the others with
+/// `message`, where `{value}` stands for the answer and `{constraint}` for
+/// "letters and digits only".
+pub fn alphanumeric_with_message(
+    message: impl Into<String>,
+) -> impl Fn(&str) -> Result<(), String> {
+    let message = message.into();
+    let constraint = Messages::default().format("alphanumeric", &[]);
+
+    move |answer| {
+        if answer.chars().all(char::is_alphanumeric) {
+            Ok(())
+        } else {
+            Err(crate::fill_message(&message, answer, &constraint))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nothing(answer: &str) -> Result<(), String> {
+        match answer {
+            "" => Ok(()),
+            _ => Err(String::from("Please answer nothing.")),
+        }
+    }
+
+    #[test]
+    fn length_should_refuse_the_answers_out_of_range() {
+        let validator = length(3..=20);
+
+        assert_eq!(validator("abc"), Ok(()));
+        assert_eq!(
+            validator("ab"),
+            Err(String::from(
+                "Please answer with between 3 and 20 characters."
+            ))
+        );
+        assert_eq!(
+            length(..=2)("abc"),
+            Err(String::from("Please answer with at most 2 characters."))
+        );
+    }
+
+    #[test]
+    fn length_in_should_use_the_translated_messages() {
+        let messages = Messages::english()
+            .set(
+                "validator-invalid",
+                "Veuillez répondre avec { $constraint }.",
+            )
+            .set("length-at-least", "au moins { $min } caractères");
+
+        assert_eq!(
+            length_in(3.., &messages)("ab"),
+            Err(String::from(
+                "Veuillez répondre avec au moins 3 caractères."
+            ))
+        );
+    }
+
+    #[test]
+    fn length_with_message_should_fill_the_placeholders() {
+        let validator = length_with_message(3..=20, "'{value}' must have {constraint}.");
+
+        assert_eq!(
+            validator("ab"),
+            Err(String::from("'ab' must have between 3 and 20 characters."))
+        );
+    }
+
+    #[test]
+    fn alphanumeric_with_message_should_fill_the_placeholders() {
+        let validator = alphanumeric_with_message("'{value}' isn't made of {constraint}.");
+
+        assert_eq!(
+            validator("a-b"),
+            Err(String::from("'a-b' isn't made of letters and digits only."))
+        );
+    }
+
+    #[test]
+    fn and_should_require_both_validators() {
+        let validator = length(3..=20).and(alphanumeric());
+
+        assert_eq!(validator("alice42"), Ok(()));
+        assert_eq!(
+            validator("alice!"),
+            Err(String::from("Please answer with letters and digits only."))
+        );
+    }
+
+    #[test]
+    fn or_should_require_either_validator() {
+        let validator = nothing.or(length(3..));
+
+        assert_eq!(validator(""), Ok(()));
+        assert_eq!(validator("abc"), Ok(()));
+        assert_eq!(
+            validator("ab"),
+            Err(String::from("Please answer with at least 3 characters."))
+        );
+    }
+
+    #[test]
+    fn map_err_should_replace_the_error_message() {
+        let validator = alphanumeric().map_err(|_| String::from("Letters and digits only."));
+
+        assert_eq!(
+            validator("!"),
+            Err(String::from("Letters and digits only."))
+        );
+    }
+
+    #[test]
+    fn all_and_any_should_combine_several_validators() {
+        let validators =
+            || -> [BoxedValidator; 2] { [Box::new(length(..=3)), Box::new(alphanumeric())] };
+
+        assert_eq!(
+            all(validators())("ab!"),
+            Err(String::from("Please answer with letters and digits only."))
+        );
+        assert_eq!(any(validators())("ab!"), Ok(()));
+    }
+
+    #[test]
+    fn not_should_invert_a_validator() {
+        let validator = not(nothing, "Please answer.");
+
+        assert_eq!(validator("alice"), Ok(()));
+        assert_eq!(validator(""), Err(String::from("Please answer.")));
+    }
+}